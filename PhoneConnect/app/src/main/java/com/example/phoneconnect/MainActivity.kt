@@ -128,6 +128,10 @@ class MainActivity : ComponentActivity() {
         val required = buildList {
             add(Manifest.permission.CALL_PHONE)
             add(Manifest.permission.READ_PHONE_STATE)
+            // Lets `dial hangup` end the call (TelecomManager.endCall)
+            add(Manifest.permission.ANSWER_PHONE_CALLS)
+            // Lets `dial sms` send texts
+            add(Manifest.permission.SEND_SMS)
             // POST_NOTIFICATIONS only required on Android 13+
             if (android.os.Build.VERSION.SDK_INT >= android.os.Build.VERSION_CODES.TIRAMISU) {
                 add(Manifest.permission.POST_NOTIFICATIONS)
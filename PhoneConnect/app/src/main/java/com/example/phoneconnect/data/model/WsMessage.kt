@@ -9,9 +9,40 @@ sealed class InboundMessage {
     data class CallCommand(
         @SerializedName("type") val type: String = "CALL",
         @SerializedName("number") val number: String,
+        @SerializedName("sim") val sim: String? = null,   // slot number or SIM label; null = phone default
+        @SerializedName("withholdCallerId") val withholdCallerId: Boolean = false,
+        @SerializedName("callerIdPrefix") val callerIdPrefix: String? = null,   // null = #31#
         @SerializedName("id") val id: String = "",   // unique command id for dedup
     ) : InboundMessage()
 
+    /** Server → App: send a text message */
+    data class SmsCommand(
+        @SerializedName("type") val type: String = "SMS",
+        @SerializedName("number") val number: String,
+        @SerializedName("text") val text: String,
+        @SerializedName("id") val id: String = "",
+    ) : InboundMessage()
+
+    /** Server → App: end the active (or ringing) call */
+    data class EndCallCommand(
+        @SerializedName("type") val type: String = "END_CALL",
+        @SerializedName("id") val id: String = "",
+    ) : InboundMessage()
+
+    /** Server → App: play DTMF tones into the active call */
+    data class DtmfCommand(
+        @SerializedName("type") val type: String = "DTMF",
+        @SerializedName("digits") val digits: String,
+        @SerializedName("id") val id: String = "",
+    ) : InboundMessage()
+
+    /** Server → App: send a USSD request */
+    data class UssdCommand(
+        @SerializedName("type") val type: String = "USSD",
+        @SerializedName("code") val code: String,
+        @SerializedName("id") val id: String = "",
+    ) : InboundMessage()
+
     /** Server → App: ping keepalive */
     data class Ping(
         @SerializedName("type") val type: String = "PING",
@@ -30,14 +61,88 @@ data class AuthMessage(
     @SerializedName("type") val type: String = "AUTH",
     @SerializedName("deviceId") val deviceId: String,
     @SerializedName("token") val token: String,
+    @SerializedName("sims") val sims: List<SimInfo> = emptyList(),
+)
+
+/**
+ * A SIM in the phone, listed by `dial devices`.
+ */
+data class SimInfo(
+    @SerializedName("slot") val slot: Int,                  // 1-based
+    @SerializedName("label") val label: String? = null,     // display name from Android settings
+    @SerializedName("carrier") val carrier: String? = null,
 )
 
 /**
  * Acknowledgement of a received command.
+ * [result] carries command-specific outcome data (e.g. [EndCallResult]).
  */
 data class AckMessage(
     @SerializedName("type") val type: String = "ACK",
     @SerializedName("id") val id: String,
+    @SerializedName("result") val result: Any? = null,
+)
+
+/**
+ * ACK result for END_CALL.
+ */
+data class EndCallResult(
+    @SerializedName("wasActive") val wasActive: Boolean,   // a call was ringing or off-hook
+    @SerializedName("ended") val ended: Boolean,           // the hang-up request was accepted
+)
+
+/**
+ * ACK result for ANSWER_CALL.
+ */
+data class AnswerResult(
+    @SerializedName("ringing") val ringing: Boolean,          // a call was ringing
+    @SerializedName("answered") val answered: Boolean,        // the answer request was accepted
+    @SerializedName("number") val number: String? = null,     // caller, when known
+    @SerializedName("reason") val reason: String? = null,     // why not, when answered is false
+)
+
+/**
+ * ACK result for REJECT_CALL.
+ */
+data class RejectResult(
+    @SerializedName("ringing") val ringing: Boolean,
+    @SerializedName("rejected") val rejected: Boolean,
+    @SerializedName("number") val number: String? = null,     // caller; the reply SMS goes here
+    @SerializedName("reason") val reason: String? = null,
+)
+
+/**
+ * ACK result for DTMF.
+ */
+data class DtmfResult(
+    @SerializedName("played") val played: Boolean,         // tones are being played into the call
+    @SerializedName("reason") val reason: String? = null,  // why not, when played is false
+)
+
+/**
+ * ACK result for MERGE.
+ */
+data class MergeResult(
+    @SerializedName("merged") val merged: Boolean,
+    @SerializedName("reason") val reason: String? = null,   // why not, when merged is false
+)
+
+/**
+ * ACK result for CONTROL (mute / unmute / speaker / hold / resume).
+ */
+data class ControlResult(
+    @SerializedName("applied") val applied: Boolean,
+    @SerializedName("reason") val reason: String? = null,    // why not, when applied is false
+    @SerializedName("state") val state: CallControlState? = null,
+)
+
+/**
+ * The ongoing call's state as left by a CONTROL command.
+ */
+data class CallControlState(
+    @SerializedName("muted") val muted: Boolean,
+    @SerializedName("speaker") val speaker: Boolean,
+    @SerializedName("onHold") val onHold: Boolean,
 )
 
 /**
@@ -45,8 +150,50 @@ data class AckMessage(
  */
 data class StatusMessage(
     @SerializedName("type") val type: String = "STATUS",
-    @SerializedName("state") val state: String,     // CALL_STARTED | CALL_ENDED | CALL_FAILED
+    @SerializedName("state") val state: String,     // CALL_STARTED | CALL_ANSWERED | CALL_ENDED | CALL_FAILED
     @SerializedName("number") val number: String? = null,
+    @SerializedName("id") val id: String? = null,       // command id of the CALL being reported
+)
+
+/**
+ * State of one call when several are up (second party added, conference).
+ */
+data class LegStatusMessage(
+    @SerializedName("type") val type: String = "LEG_STATUS",
+    @SerializedName("number") val number: String,
+    @SerializedName("state") val state: String,           // DIALING | RINGING | ACTIVE | HOLDING | DISCONNECTED
+    @SerializedName("conference") val conference: Boolean,  // part of a merged conference
+    @SerializedName("id") val id: String? = null,           // command id of the CALL that placed this leg
+)
+
+/**
+ * A call ringing on this phone, and how it ended up (`dial watch --incoming`).
+ */
+data class IncomingCallMessage(
+    @SerializedName("type") val type: String = "INCOMING",
+    @SerializedName("state") val state: String,          // RINGING | ANSWERED | MISSED
+    @SerializedName("number") val number: String? = null, // null when the caller withholds it
+    @SerializedName("name") val name: String? = null,     // contact name, when the phone knows one
+)
+
+/**
+ * Progress report for an SMS command.
+ */
+data class SmsStatusMessage(
+    @SerializedName("type") val type: String = "SMS_STATUS",
+    @SerializedName("id") val id: String,               // command id of the SMS being reported
+    @SerializedName("state") val state: String,         // SMS_SENT | SMS_DELIVERED | SMS_FAILED
+    @SerializedName("reason") val reason: String? = null,
+)
+
+/**
+ * Carrier reply to a USSD command.
+ */
+data class UssdResponseMessage(
+    @SerializedName("type") val type: String = "USSD_RESPONSE",
+    @SerializedName("id") val id: String,               // command id of the USSD request
+    @SerializedName("ok") val ok: Boolean,
+    @SerializedName("message") val message: String,     // reply text, or why the request failed
 )
 
 /**
@@ -60,6 +207,13 @@ data class PongMessage(
 
 enum class CallState(val raw: String) {
     CALL_STARTED("CALL_STARTED"),
+    CALL_ANSWERED("CALL_ANSWERED"),
     CALL_ENDED("CALL_ENDED"),
     CALL_FAILED("CALL_FAILED"),
 }
+
+enum class SmsState(val raw: String) {
+    SMS_SENT("SMS_SENT"),
+    SMS_DELIVERED("SMS_DELIVERED"),
+    SMS_FAILED("SMS_FAILED"),
+}
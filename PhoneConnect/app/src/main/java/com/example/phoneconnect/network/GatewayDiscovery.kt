@@ -53,7 +53,7 @@ sealed class DiscoveryState {
  * Algorithm:
  *  1. [startDiscovery] → NsdManager begins listening for `_phoneconnect._tcp` announcements
  *  2. First matching service found → [resolveService] → get host + port
- *  3. Build "ws://<host>:<port>/ws" URL → emit [DiscoveryState.Found] → call [onFound]
+ *  3. Build "ws://<host>:<port>/ws" URL ("wss://" if the TXT record says so) → emit [DiscoveryState.Found] → call [onFound]
  *  4. Auto-stop discovery after first successful resolve (one gateway is enough)
  *
  * @param context   Android context (used to obtain NsdManager)
@@ -190,7 +190,7 @@ class GatewayDiscovery(
                             return
                         }
                     val port = resolvedInfo.port
-                    handleResolved(host, port)
+                    handleResolved(host, port, isTls(resolvedInfo))
                     // Unregister now that we have what we need
                     try { nsdManager.unregisterServiceInfoCallback(this) } catch (_: Exception) {}
                 }
@@ -229,14 +229,19 @@ class GatewayDiscovery(
                         isResolving = false
                         return
                     }
-                handleResolved(host, resolved.port)
+                handleResolved(host, resolved.port, isTls(resolved))
             }
         })
     }
 
+    /** The gateway advertises `protocol=wss` in its TXT record when it serves TLS. */
+    private fun isTls(info: NsdServiceInfo): Boolean =
+        info.attributes["protocol"]?.let { String(it, Charsets.UTF_8) } == "wss"
+
     /** Common post-resolution handler — builds URL, emits state, fires callback. */
-    private fun handleResolved(host: String, port: Int) {
-        val wsUrl = "ws://$host:$port/ws"
+    private fun handleResolved(host: String, port: Int, tls: Boolean) {
+        val scheme = if (tls) "wss" else "ws"
+        val wsUrl = "$scheme://$host:$port/ws"
         Log.i(TAG, "Gateway resolved: $wsUrl")
 
         _state.value = DiscoveryState.Found(wsUrl, host, port)
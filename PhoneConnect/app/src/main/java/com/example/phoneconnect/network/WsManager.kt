@@ -2,9 +2,16 @@ package com.example.phoneconnect.network
 
 import android.util.Log
 import com.example.phoneconnect.data.model.AckMessage
+import com.example.phoneconnect.data.model.AnswerResult
 import com.example.phoneconnect.data.model.AuthMessage
+import com.example.phoneconnect.data.model.ControlResult
+import com.example.phoneconnect.data.model.DtmfResult
+import com.example.phoneconnect.data.model.EndCallResult
 import com.example.phoneconnect.data.model.InboundMessage
+import com.example.phoneconnect.data.model.MergeResult
 import com.example.phoneconnect.data.model.PongMessage
+import com.example.phoneconnect.data.model.RejectResult
+import com.example.phoneconnect.data.model.SimInfo
 import com.google.gson.Gson
 import com.google.gson.JsonObject
 import com.google.gson.JsonParser
@@ -41,6 +48,9 @@ private const val PING_INTERVAL_MS      = 25_000L
 private const val MAX_RECONNECT_DELAY_MS = 60_000L
 private const val BASE_RECONNECT_DELAY_MS = 1_000L
 
+/** In-call actions a CONTROL command may carry. */
+private val CONTROL_ACTIONS = setOf("mute", "unmute", "speaker", "hold", "resume")
+
 // ─── Connection state ─────────────────────────────────────────────────────────
 
 sealed class ConnectionState {
@@ -64,7 +74,16 @@ sealed class ConnectionState {
  */
 class WsManager(
     private val gson: Gson,
-    private val onCallCommand: (number: String, commandId: String) -> Unit,
+    private val onCallCommand: (command: InboundMessage.CallCommand) -> Unit,
+    private val onEndCallCommand: () -> EndCallResult,
+    private val onAnswerCommand: () -> AnswerResult,
+    private val onRejectCommand: (sms: String?, commandId: String) -> RejectResult,
+    private val onSmsCommand: (number: String, text: String, commandId: String) -> Unit,
+    private val onDtmfCommand: (digits: String) -> DtmfResult,
+    private val onControlCommand: (action: String) -> ControlResult,
+    private val onMergeCommand: () -> MergeResult,
+    private val onUssdCommand: (code: String, commandId: String) -> Unit,
+    private val reportSims: () -> List<SimInfo>,
 ) {
     // ── State exposed to UI ───────────────────────────────────────────────────
 
@@ -161,9 +180,9 @@ class WsManager(
             _state.value = ConnectionState.Connected
 
             // Authenticate immediately
-            val auth = AuthMessage(deviceId = deviceId, token = token)
+            val auth = AuthMessage(deviceId = deviceId, token = token, sims = reportSims())
             webSocket.send(gson.toJson(auth))
-            log("→ AUTH deviceId=$deviceId")
+            log("→ AUTH deviceId=$deviceId sims=${auth.sims.size}")
 
             startPingLoop()
         }
@@ -209,6 +228,13 @@ class WsManager(
                 "CALL" -> {
                     val number    = obj.get("number")?.asString ?: return
                     val commandId = obj.get("id")?.asString ?: ""
+                    val command   = InboundMessage.CallCommand(
+                        number           = number,
+                        sim              = obj.get("sim")?.takeUnless { it.isJsonNull }?.asString,
+                        withholdCallerId = obj.get("withholdCallerId")?.asBoolean ?: false,
+                        callerIdPrefix   = obj.get("callerIdPrefix")?.takeUnless { it.isJsonNull }?.asString,
+                        id               = commandId,
+                    )
 
                     if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
                         log("Duplicate command $commandId — ignored")
@@ -221,11 +247,151 @@ class WsManager(
                         log("Invalid phone number: $number")
                         return
                     }
+                    if (command.callerIdPrefix?.let(::isValidCallerIdPrefix) == false) {
+                        log("Invalid caller ID prefix: ${command.callerIdPrefix}")
+                        return
+                    }
 
                     // ACK before triggering the call
                     if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId))
 
-                    onCallCommand(number, commandId)
+                    onCallCommand(command)
+                }
+
+                "SMS" -> {
+                    val number    = obj.get("number")?.asString ?: return
+                    val text      = obj.get("text")?.asString ?: return
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    if (!isValidPhone(number) || text.isBlank()) {
+                        log("Invalid SMS command for $number")
+                        return
+                    }
+
+                    // ACK on receipt; sent/delivered follow as SMS_STATUS
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId))
+
+                    onSmsCommand(number, text, commandId)
+                }
+
+                "END_CALL" -> {
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    // ACK *after* hanging up so the result can tell the
+                    // gateway whether there was anything to end
+                    val result = onEndCallCommand()
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId, result = result))
+                }
+
+                "ANSWER_CALL" -> {
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    val result = onAnswerCommand()
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId, result = result))
+                }
+
+                "REJECT_CALL" -> {
+                    val sms       = obj.get("sms")?.takeIf { it.isJsonPrimitive }?.asString
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    val result = onRejectCommand(sms?.ifBlank { null }, commandId)
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId, result = result))
+                }
+
+                "DTMF" -> {
+                    val digits    = obj.get("digits")?.asString ?: return
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    if (!isValidDtmf(digits)) {
+                        log("Invalid DTMF digits: $digits")
+                        return
+                    }
+
+                    val result = onDtmfCommand(digits)
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId, result = result))
+                }
+
+                "CONTROL" -> {
+                    val action    = obj.get("action")?.asString?.lowercase() ?: return
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    if (action !in CONTROL_ACTIONS) {
+                        log("Invalid control action: $action")
+                        return
+                    }
+
+                    val result = onControlCommand(action)
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId, result = result))
+                }
+
+                "MERGE" -> {
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    val result = onMergeCommand()
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId, result = result))
+                }
+
+                "USSD" -> {
+                    val code      = obj.get("code")?.asString ?: return
+                    val commandId = obj.get("id")?.asString ?: ""
+
+                    if (commandId.isNotBlank() && processedIds.containsKey(commandId)) {
+                        log("Duplicate command $commandId — ignored")
+                        return
+                    }
+                    if (commandId.isNotBlank()) processedIds[commandId] = true
+
+                    if (!isValidUssd(code)) {
+                        log("Invalid USSD code: $code")
+                        return
+                    }
+
+                    // ACK on receipt; the carrier's reply follows as USSD_RESPONSE
+                    if (commandId.isNotBlank()) sendMessage(AckMessage(id = commandId))
+
+                    onUssdCommand(code, commandId)
                 }
 
                 "PING" -> {
@@ -285,6 +451,18 @@ class WsManager(
     private fun isValidPhone(number: String): Boolean =
         number.matches(Regex("^\\+?[1-9]\\d{6,14}$"))
 
+    /** Carrier caller-ID codes such as #31# or *67. */
+    private fun isValidCallerIdPrefix(prefix: String): Boolean =
+        prefix.matches(Regex("^[0-9*#]{1,8}$"))
+
+    /** DTMF keys: 0-9, *, # and the A–D column. */
+    private fun isValidDtmf(digits: String): Boolean =
+        digits.matches(Regex("^[0-9*#A-D]{1,64}$"))
+
+    /** USSD strings: `*` or `#` first, `#` last, digits / `*` / `#` between. */
+    private fun isValidUssd(code: String): Boolean =
+        code.length <= 182 && code.matches(Regex("^[*#][0-9*#]*\\d[0-9*#]*#$"))
+
     private fun log(message: String) {
         val timestamped = "[${System.currentTimeMillis()}] $message"
         Log.d(TAG, timestamped)
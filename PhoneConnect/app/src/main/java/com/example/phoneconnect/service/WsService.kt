@@ -21,6 +21,9 @@ import com.example.phoneconnect.network.DiscoveryState
 import com.example.phoneconnect.network.GatewayDiscovery
 import com.example.phoneconnect.network.WsManager
 import com.example.phoneconnect.telephony.CallManager
+import com.example.phoneconnect.telephony.SimDirectory
+import com.example.phoneconnect.telephony.SmsSender
+import com.example.phoneconnect.telephony.UssdRunner
 import com.google.gson.Gson
 import kotlinx.coroutines.CoroutineScope
 import kotlinx.coroutines.Dispatchers
@@ -59,6 +62,10 @@ class WsService : Service() {
     lateinit var callManager: CallManager
         private set
 
+    private lateinit var smsSender: SmsSender
+    private lateinit var ussdRunner: UssdRunner
+    private lateinit var simDirectory: SimDirectory
+
     private lateinit var prefs: AppPreferences
     private lateinit var discovery: GatewayDiscovery
 
@@ -68,6 +75,7 @@ class WsService : Service() {
         super.onCreate()
         Log.d(TAG, "onCreate")
         prefs = AppPreferences(applicationContext)
+        simDirectory = SimDirectory(applicationContext)
 
         // Auto-discovery: when a gateway is found on the LAN, save the URL to prefs.
         // loadPrefsAndConnect's collectLatest will pick up the new URL and reconnect.
@@ -83,12 +91,59 @@ class WsService : Service() {
 
         wsManager = WsManager(
             gson = Gson(),
-            onCallCommand = { number, commandId ->
-                Log.d(TAG, "CALL command received — number=$number id=$commandId")
-                callManager.initiateCall(number)
-            }
+            onCallCommand = { command ->
+                Log.d(TAG, "CALL command received — number=${command.number} sim=${command.sim} " +
+                    "withheld=${command.withholdCallerId} id=${command.id}")
+                callManager.initiateCall(
+                    number = command.number,
+                    sim = command.sim,
+                    withholdCallerId = command.withholdCallerId,
+                    callerIdPrefix = command.callerIdPrefix,
+                    commandId = command.id,
+                )
+            },
+            onEndCallCommand = {
+                Log.d(TAG, "END_CALL command received")
+                callManager.endCall()
+            },
+            onAnswerCommand = {
+                Log.d(TAG, "ANSWER_CALL command received")
+                callManager.answerCall()
+            },
+            onRejectCommand = { sms, commandId ->
+                Log.d(TAG, "REJECT_CALL command received — reply=${sms != null} id=$commandId")
+                callManager.rejectCall().also { result ->
+                    // The reply's SMS_STATUS reports carry the REJECT_CALL id
+                    if (sms != null && result.rejected && result.number != null) {
+                        smsSender.send(result.number, sms, commandId)
+                    }
+                }
+            },
+            onSmsCommand = { number, text, commandId ->
+                Log.d(TAG, "SMS command received — number=$number id=$commandId")
+                smsSender.send(number, text, commandId)
+            },
+            onDtmfCommand = { digits ->
+                Log.d(TAG, "DTMF command received — ${digits.length} digit(s)")
+                callManager.sendDtmf(digits)
+            },
+            onControlCommand = { action ->
+                Log.d(TAG, "CONTROL command received — $action")
+                callManager.control(action)
+            },
+            onMergeCommand = {
+                Log.d(TAG, "MERGE command received")
+                callManager.merge()
+            },
+            onUssdCommand = { code, commandId ->
+                Log.d(TAG, "USSD command received — id=$commandId")
+                ussdRunner.send(code, commandId)
+            },
+            reportSims = { simDirectory.list() },
         )
-        callManager = CallManager(applicationContext, wsManager)
+        callManager = CallManager(applicationContext, wsManager, simDirectory)
+        smsSender = SmsSender(applicationContext, wsManager)
+        ussdRunner = UssdRunner(applicationContext, wsManager)
 
         createNotificationChannel()
         // Pass the service type on API 29+ so the OS knows this is a data-sync service.
@@ -143,6 +198,7 @@ class WsService : Service() {
         discovery.destroy()
         wsManager.destroy()
         callManager.destroy()
+        smsSender.destroy()
         ServiceBus.setServiceRunning(false)
         serviceScope.cancel()
         super.onDestroy()
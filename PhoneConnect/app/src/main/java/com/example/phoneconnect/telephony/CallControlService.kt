@@ -0,0 +1,100 @@
+package com.example.phoneconnect.telephony
+
+import android.telecom.Call
+import android.telecom.InCallService
+import android.util.Log
+import java.util.concurrent.CopyOnWriteArrayList
+
+private const val TAG = "CallControlService"
+
+/**
+ * Non-UI [InCallService] that gives PhoneConnect a handle on ongoing calls.
+ *
+ * Only an [InCallService] can act on a call once it is connected (DTMF tones,
+ * hold, mute and audio routing); [TelecomManager][android.telecom.TelecomManager]
+ * alone can just place and end calls.  Telecom binds this service while any call exists
+ * as long as the app holds MANAGE_ONGOING_CALLS (granted to companion apps);
+ * the default dialer keeps showing its own in-call UI.
+ *
+ * Every state change of every call is passed to [ActiveCalls.onLegChanged],
+ * which is how conferences report each leg separately.
+ */
+class CallControlService : InCallService() {
+
+    override fun onCreate() {
+        super.onCreate()
+        ActiveCalls.service = this
+    }
+
+    private val callback = object : Call.Callback() {
+        override fun onStateChanged(call: Call, state: Int) {
+            ActiveCalls.onLegChanged?.invoke(call)
+        }
+
+        override fun onParentChanged(call: Call, parent: Call?) {
+            ActiveCalls.onLegChanged?.invoke(call)
+        }
+    }
+
+    override fun onCallAdded(call: Call) {
+        Log.d(TAG, "Call added (state=${call.details.state})")
+        ActiveCalls.add(call)
+        call.registerCallback(callback)
+        ActiveCalls.onLegChanged?.invoke(call)
+    }
+
+    override fun onCallRemoved(call: Call) {
+        Log.d(TAG, "Call removed")
+        call.unregisterCallback(callback)
+        ActiveCalls.remove(call)
+    }
+
+    override fun onDestroy() {
+        ActiveCalls.clear()
+        ActiveCalls.service = null
+        super.onDestroy()
+    }
+}
+
+/**
+ * Calls currently known to [CallControlService].  Empty when the service is
+ * not bound (no call, or the app lacks the call-control permission).
+ */
+object ActiveCalls {
+    private val calls = CopyOnWriteArrayList<Call>()
+
+    @Volatile var bound = false
+        private set
+
+    /** Called on every state change of any call (set by [CallManager]). */
+    @Volatile var onLegChanged: ((Call) -> Unit)? = null
+
+    /** The bound service; mute and audio routing go through it. */
+    @Volatile var service: CallControlService? = null
+        internal set
+
+    internal fun add(call: Call) {
+        bound = true
+        calls.add(call)
+    }
+
+    internal fun remove(call: Call) {
+        calls.remove(call)
+    }
+
+    internal fun clear() {
+        bound = false
+        calls.clear()
+    }
+
+    /** The call in the foreground (connected and not on hold), if any. */
+    fun foreground(): Call? = calls.lastOrNull { it.details.state == Call.STATE_ACTIVE }
+
+    /** Every call Telecom currently knows about, oldest first. */
+    fun all(): List<Call> = calls.toList()
+
+    /** The connected call, whether in the foreground or on hold. */
+    fun current(): Call? = calls.lastOrNull {
+        it.details.state == Call.STATE_ACTIVE || it.details.state == Call.STATE_HOLDING
+    }
+}
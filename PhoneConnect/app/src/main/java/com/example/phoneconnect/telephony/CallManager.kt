@@ -3,34 +3,74 @@ package com.example.phoneconnect.telephony
 import android.content.Context
 import android.content.Intent
 import android.net.Uri
+import android.telecom.Call
+import android.telecom.CallAudioState
+import android.telecom.TelecomManager
+import android.telecom.VideoProfile
 import android.telephony.PhoneStateListener
 import android.telephony.TelephonyManager
 import android.util.Log
+import com.example.phoneconnect.data.model.AnswerResult
+import com.example.phoneconnect.data.model.CallControlState
 import com.example.phoneconnect.data.model.CallState
+import com.example.phoneconnect.data.model.ControlResult
+import com.example.phoneconnect.data.model.DtmfResult
+import com.example.phoneconnect.data.model.EndCallResult
+import com.example.phoneconnect.data.model.IncomingCallMessage
+import com.example.phoneconnect.data.model.LegStatusMessage
+import com.example.phoneconnect.data.model.MergeResult
+import com.example.phoneconnect.data.model.RejectResult
 import com.example.phoneconnect.data.model.StatusMessage
 import com.example.phoneconnect.network.WsManager
 import kotlinx.coroutines.CoroutineScope
 import kotlinx.coroutines.Dispatchers
 import kotlinx.coroutines.SupervisorJob
+import kotlinx.coroutines.delay
 import kotlinx.coroutines.flow.MutableStateFlow
 import kotlinx.coroutines.flow.StateFlow
 import kotlinx.coroutines.flow.asStateFlow
 import kotlinx.coroutines.launch
+import java.util.concurrent.ConcurrentHashMap
 
 private const val TAG = "CallManager"
 
+/**
+ * Dialled before the number to withhold caller ID when the gateway doesn't
+ * name a carrier code — the GSM per-call CLIR code, which the telephony stack
+ * itself interprets on most networks.
+ */
+private const val DEFAULT_CALLER_ID_PREFIX = "#31#"
+
+/** How long each DTMF tone sounds, and the silence between tones. */
+private const val DTMF_TONE_MS = 150L
+private const val DTMF_GAP_MS  = 100L
+
 /**
  * Manages outgoing call initiation and monitors call lifecycle via [TelephonyManager].
  *
  * Call flow:
  *   1. [initiateCall] fires ACTION_CALL intent.
  *   2. [CallStateObserver] (PhoneStateListener) watches for OFFHOOK → reports CALL_STARTED.
- *   3. When state returns to IDLE after OFFHOOK → reports CALL_ENDED.
- *   4. Any intent failure → reports CALL_FAILED.
+ *      OFFHOOK comes as soon as dialling begins, so …
+ *   3. … [CallControlService] reports the call going ACTIVE (the far end
+ *      picked up) → CALL_ANSWERED.  Post-dial keys wait for this.
+ *   4. When state returns to IDLE after OFFHOOK → reports CALL_ENDED.
+ *   5. Any intent failure → reports CALL_FAILED.
+ *
+ * Calls ringing on the phone are reported as INCOMING (RINGING, then
+ * ANSWERED or MISSED) so `dial watch --incoming` can show them; [answerCall]
+ * and [rejectCall] pick them up or decline them.
+ *
+ * [endCall] hangs up on request of the gateway (`dial hangup`); the resulting
+ * IDLE transition reports CALL_ENDED as usual.  [sendDtmf] plays key presses
+ * into the connected call and [control] mutes / holds it; [merge] joins a
+ * second call into a conference, whose legs are reported as LEG_STATUS.  All
+ * of these go through [CallControlService].
  */
 class CallManager(
     private val context: Context,
     private val wsManager: WsManager,
+    private val simDirectory: SimDirectory,
 ) {
     private val scope = CoroutineScope(SupervisorJob() + Dispatchers.Main)
     private val telephonyManager =
@@ -39,12 +79,25 @@ class CallManager(
     private val _callState = MutableStateFlow<CallLifecycle>(CallLifecycle.Idle)
     val callState: StateFlow<CallLifecycle> = _callState.asStateFlow()
 
+    private val telecomManager =
+        context.getSystemService(Context.TELECOM_SERVICE) as TelecomManager
+
     private val phoneStateListener = CallStateObserver()
+    /** Last state seen by [CallStateObserver]; avoids the deprecated TelephonyManager.callState. */
+    @Volatile private var lastTelephonyState = TelephonyManager.CALL_STATE_IDLE
     private var activeNumber: String? = null
+    private var activeCommandId: String? = null
+    /** Whether the call placed by [initiateCall] has been reported CALL_ANSWERED. */
+    @Volatile private var answered = false
+    /** Number of the call ringing now ("" when withheld), null when none is. */
+    @Volatile private var ringingNumber: String? = null
+    /** Command ids of calls placed while another was up, keyed by number. */
+    private val legCommands = ConcurrentHashMap<String, String>()
 
     init {
         @Suppress("DEPRECATION")
         telephonyManager.listen(phoneStateListener, PhoneStateListener.LISTEN_CALL_STATE)
+        ActiveCalls.onLegChanged = ::onCallChanged
     }
 
     // ── Public API ────────────────────────────────────────────────────────────
@@ -52,44 +105,331 @@ class CallManager(
     /**
      * Initiates a cellular call via ACTION_CALL intent.
      * Requires CALL_PHONE permission to be granted at runtime before this call.
+     *
+     * [sim] picks the line on dual-SIM phones (see [SimDirectory]); null uses
+     * the phone's default.  An unknown SIM fails the call rather than silently
+     * calling from the other line.
+     *
+     * With [withholdCallerId], [callerIdPrefix] (or [DEFAULT_CALLER_ID_PREFIX])
+     * is dialled in front of the number; Android has no public per-call API
+     * for hiding the caller's number.
+     *
+     * [commandId] is echoed in every STATUS report for this call so the gateway
+     * can correlate lifecycle updates with the originating CALL command.
+     *
+     * While a call is already up the new one is a second leg (`dial conference`):
+     * Telecom holds the first call, the leg is reported through LEG_STATUS, and
+     * the first call's STATUS tracking is left alone.
      */
-    fun initiateCall(number: String) {
-        activeNumber = number
-        Log.d(TAG, "Initiating call to $number")
+    fun initiateCall(
+        number: String,
+        sim: String? = null,
+        withholdCallerId: Boolean = false,
+        callerIdPrefix: String? = null,
+        commandId: String = "",
+    ) {
+        val addingLeg = lastTelephonyState != TelephonyManager.CALL_STATE_IDLE
+        if (addingLeg) {
+            commandId.ifBlank { null }?.let { legCommands[number] = it }
+        } else {
+            activeNumber = number
+            activeCommandId = commandId.ifBlank { null }
+            answered = false
+        }
+        fun fail(reason: String) {
+            if (addingLeg) {
+                legCommands.remove(number)
+                val msg = StatusMessage(state = CallState.CALL_FAILED.raw, number = number, id = commandId.ifBlank { null })
+                wsManager.sendMessage(msg)
+            } else {
+                reportStatus(CallState.CALL_FAILED, number)
+                _callState.value = CallLifecycle.Failed(reason)
+            }
+        }
+
+        Log.d(TAG, "Initiating call to $number (sim=${sim ?: "default"}, withheld=$withholdCallerId, leg=$addingLeg)")
         try {
-            val intent = Intent(Intent.ACTION_CALL, Uri.parse("tel:$number")).apply {
+            val account = sim?.let { selector ->
+                simDirectory.phoneAccountFor(selector) ?: run {
+                    Log.e(TAG, "No active SIM matches \"$selector\"")
+                    fail("No SIM \"$selector\"")
+                    return
+                }
+            }
+            val dialled = if (withholdCallerId) {
+                (callerIdPrefix ?: DEFAULT_CALLER_ID_PREFIX) + number
+            } else {
+                number
+            }
+            // fromParts encodes the '#' of prefixes such as #31#
+            val intent = Intent(Intent.ACTION_CALL, Uri.fromParts("tel", dialled, null)).apply {
                 addFlags(Intent.FLAG_ACTIVITY_NEW_TASK)
+                account?.let { putExtra(TelecomManager.EXTRA_PHONE_ACCOUNT_HANDLE, it) }
             }
             context.startActivity(intent)
-            _callState.value = CallLifecycle.Calling(number)
+            if (!addingLeg) _callState.value = CallLifecycle.Calling(number)
         } catch (e: SecurityException) {
             Log.e(TAG, "Permission denied for CALL_PHONE: ${e.message}")
-            reportStatus(CallState.CALL_FAILED, number)
-            _callState.value = CallLifecycle.Failed("Permission denied")
+            fail("Permission denied")
         } catch (e: Exception) {
             Log.e(TAG, "Failed to initiate call: ${e.message}")
-            reportStatus(CallState.CALL_FAILED, number)
-            _callState.value = CallLifecycle.Failed(e.message ?: "Unknown error")
+            fail(e.message ?: "Unknown error")
+        }
+    }
+
+    /**
+     * Ends the ongoing or ringing call, if any.
+     * Requires ANSWER_PHONE_CALLS to be granted at runtime.
+     */
+    fun endCall(): EndCallResult {
+        val wasActive = lastTelephonyState != TelephonyManager.CALL_STATE_IDLE
+        if (!wasActive) {
+            Log.d(TAG, "END_CALL: no active call")
+            return EndCallResult(wasActive = false, ended = false)
+        }
+        return try {
+            @Suppress("DEPRECATION")   // still the only non-dialer way to hang up
+            val ended = telecomManager.endCall()
+            Log.d(TAG, "END_CALL: endCall() returned $ended")
+            EndCallResult(wasActive = true, ended = ended)
+        } catch (e: SecurityException) {
+            Log.e(TAG, "Permission denied for ANSWER_PHONE_CALLS: ${e.message}")
+            EndCallResult(wasActive = true, ended = false)
+        }
+    }
+
+    /**
+     * Picks up the ringing call (`dial answer`); a call already up is put on
+     * hold.  Goes through [CallControlService] when bound, otherwise through
+     * TelecomManager, which needs ANSWER_PHONE_CALLS.
+     */
+    fun answerCall(): AnswerResult {
+        val number = ringingNumber?.ifBlank { null }
+        if (lastTelephonyState != TelephonyManager.CALL_STATE_RINGING) {
+            Log.d(TAG, "ANSWER_CALL: nothing ringing")
+            return AnswerResult(ringing = false, answered = false, reason = "no call is ringing")
+        }
+        return try {
+            val call = ActiveCalls.all().firstOrNull { it.details.state == Call.STATE_RINGING }
+            if (call != null) {
+                call.answer(VideoProfile.STATE_AUDIO_ONLY)
+            } else {
+                @Suppress("DEPRECATION")   // still the only non-dialer way to answer
+                telecomManager.acceptRingingCall()
+            }
+            Log.d(TAG, "ANSWER_CALL: answered")
+            AnswerResult(ringing = true, answered = true, number = number)
+        } catch (e: SecurityException) {
+            Log.e(TAG, "Permission denied for ANSWER_PHONE_CALLS: ${e.message}")
+            AnswerResult(ringing = true, answered = false, number = number, reason = "permission denied")
+        }
+    }
+
+    /**
+     * Declines the ringing call (`dial reject`).  The caller's number is
+     * returned so a reply text can be sent to it.
+     */
+    fun rejectCall(): RejectResult {
+        val number = ringingNumber?.ifBlank { null }
+        if (lastTelephonyState != TelephonyManager.CALL_STATE_RINGING) {
+            Log.d(TAG, "REJECT_CALL: nothing ringing")
+            return RejectResult(ringing = false, rejected = false, reason = "no call is ringing")
+        }
+        return try {
+            val call = ActiveCalls.all().firstOrNull { it.details.state == Call.STATE_RINGING }
+            val rejected = if (call != null) {
+                call.reject(false, null)
+                true
+            } else {
+                @Suppress("DEPRECATION")   // ends the ringing call, i.e. declines it
+                telecomManager.endCall()
+            }
+            Log.d(TAG, "REJECT_CALL: rejected=$rejected")
+            RejectResult(ringing = true, rejected = rejected, number = number)
+        } catch (e: SecurityException) {
+            Log.e(TAG, "Permission denied for ANSWER_PHONE_CALLS: ${e.message}")
+            RejectResult(ringing = true, rejected = false, number = number, reason = "permission denied")
+        }
+    }
+
+    /**
+     * Plays [digits] as DTMF tones into the connected call (`dial dtmf`).
+     * Returns immediately; the tones follow one after another.
+     */
+    fun sendDtmf(digits: String): DtmfResult {
+        val call = ActiveCalls.foreground()
+        if (call == null) {
+            val reason = noCallReason()
+            Log.d(TAG, "DTMF: $reason")
+            return DtmfResult(played = false, reason = reason)
         }
+        scope.launch {
+            for (digit in digits) {
+                call.playDtmfTone(digit)
+                delay(DTMF_TONE_MS)
+                call.stopDtmfTone()
+                delay(DTMF_GAP_MS)
+            }
+        }
+        Log.d(TAG, "DTMF: playing ${digits.length} tone(s)")
+        return DtmfResult(played = true)
+    }
+
+    /**
+     * Merges the device's calls into one conference (`dial conference`),
+     * normally the active call and the one on hold.  Returns once Telecom
+     * has been asked; the legs report the result through LEG_STATUS.
+     */
+    fun merge(): MergeResult {
+        val active = ActiveCalls.foreground()
+            ?: return MergeResult(merged = false, reason = noCallReason())
+        val others = ActiveCalls.all().filter { it != active && it.details.state == Call.STATE_HOLDING }
+
+        when {
+            active.details.can(Call.Details.CAPABILITY_MERGE_CONFERENCE) -> active.mergeConference()
+            active.conferenceableCalls.isNotEmpty() -> active.conference(active.conferenceableCalls.first())
+            others.isEmpty() -> return MergeResult(merged = false, reason = "no second call to merge with")
+            else -> return MergeResult(merged = false, reason = "the network does not allow merging these calls")
+        }
+        Log.d(TAG, "MERGE: conference requested")
+        return MergeResult(merged = true)
+    }
+
+    /**
+     * Mutes, switches the speakerphone or holds the connected call
+     * (`dial control`).  [action] is one of mute, unmute, speaker (toggle),
+     * hold and resume.  The returned state is the one the action leaves the
+     * call in; Telecom applies it asynchronously.
+     */
+    @Suppress("DEPRECATION")   // callAudioState / setAudioRoute: still work on API 34+
+    fun control(action: String): ControlResult {
+        val call = ActiveCalls.current()
+        val service = ActiveCalls.service
+        if (call == null || service == null) {
+            val reason = noCallReason()
+            Log.d(TAG, "CONTROL $action: $reason")
+            return ControlResult(applied = false, reason = reason)
+        }
+
+        val audio = service.callAudioState
+        var muted = audio?.isMuted ?: false
+        var speaker = audio?.route == CallAudioState.ROUTE_SPEAKER
+        var onHold = call.details.state == Call.STATE_HOLDING
+        when (action) {
+            "mute", "unmute" -> {
+                muted = action == "mute"
+                service.setMuted(muted)
+            }
+            "speaker" -> {
+                speaker = !speaker
+                // Back to Bluetooth when available, so HFP audio keeps flowing
+                val bluetooth = (audio?.supportedRouteMask ?: 0) and CallAudioState.ROUTE_BLUETOOTH != 0
+                service.setAudioRoute(
+                    when {
+                        speaker -> CallAudioState.ROUTE_SPEAKER
+                        bluetooth -> CallAudioState.ROUTE_BLUETOOTH
+                        else -> CallAudioState.ROUTE_WIRED_OR_EARPIECE
+                    }
+                )
+            }
+            "hold" -> {
+                if (!call.details.can(Call.Details.CAPABILITY_HOLD)) {
+                    return ControlResult(applied = false, reason = "the network does not allow hold")
+                }
+                call.hold()
+                onHold = true
+            }
+            "resume" -> {
+                if (!onHold) return ControlResult(applied = false, reason = "call is not on hold")
+                call.unhold()
+                onHold = false
+            }
+            else -> return ControlResult(applied = false, reason = "unknown action $action")
+        }
+        Log.d(TAG, "CONTROL $action: muted=$muted speaker=$speaker onHold=$onHold")
+        return ControlResult(applied = true, state = CallControlState(muted, speaker, onHold))
     }
 
     /**
      * Unregister the phone state listener.  Call from Service#onDestroy().
      */
     fun destroy() {
+        ActiveCalls.onLegChanged = null
         @Suppress("DEPRECATION")
         telephonyManager.listen(phoneStateListener, PhoneStateListener.LISTEN_NONE)
     }
 
     // ── Helpers ───────────────────────────────────────────────────────────────
 
+    /** Why there is no call to act on, for DTMF and CONTROL results. */
+    private fun noCallReason(): String = when {
+        lastTelephonyState == TelephonyManager.CALL_STATE_IDLE -> "no active call"
+        !ActiveCalls.bound -> "call control unavailable — grant PhoneConnect call control"
+        else -> "call is not connected yet"
+    }
+
+    /**
+     * Every state change of every call: reported as a leg, and as
+     * CALL_ANSWERED once the call placed by [initiateCall] connects.
+     */
+    private fun onCallChanged(call: Call) {
+        reportLeg(call)
+        if (answered || call.details.state != Call.STATE_ACTIVE) return
+        // Carrier prefixes (#31#) may precede the number in the handle
+        val number = call.details.handle?.schemeSpecificPart ?: return
+        if (activeNumber?.let(number::endsWith) != true) return
+        answered = true
+        Log.d(TAG, "Call state: ACTIVE")
+        reportStatus(CallState.CALL_ANSWERED)
+    }
+
+    /**
+     * Report one call's state as LEG_STATUS.  The conference itself is not a
+     * leg — its participants are reported, flagged with `conference`.
+     */
+    private fun reportLeg(call: Call) {
+        if (call.details.hasProperty(Call.Details.PROPERTY_CONFERENCE)) return
+        // Carrier prefixes (#31#) may precede the number in the handle
+        val number = call.details.handle?.schemeSpecificPart ?: return
+        val state = when (call.details.state) {
+            Call.STATE_DIALING, Call.STATE_CONNECTING -> "DIALING"
+            Call.STATE_RINGING -> "RINGING"
+            Call.STATE_ACTIVE -> "ACTIVE"
+            Call.STATE_HOLDING -> "HOLDING"
+            Call.STATE_DISCONNECTED -> "DISCONNECTED"
+            else -> return
+        }
+        val leg = legCommands.keys.firstOrNull { number.endsWith(it) }
+        val id = leg?.let { legCommands[it] }
+            ?: activeCommandId.takeIf { activeNumber?.let(number::endsWith) == true }
+        if (state == "DISCONNECTED" && leg != null) legCommands.remove(leg)
+
+        val msg = LegStatusMessage(number = number, state = state, conference = call.parent != null, id = id)
+        val sent = wsManager.sendMessage(msg)
+        Log.d(TAG, "Leg reported: $number $state (sent=$sent)")
+    }
+
+    /**
+     * Report an incoming call.  The caller's contact name comes from Telecom,
+     * so it is only known while [CallControlService] is bound.
+     */
+    private fun reportIncoming(state: String, number: String?) {
+        val name = ActiveCalls.all()
+            .firstOrNull { it.details.state == Call.STATE_RINGING }
+            ?.details?.let { it.contactDisplayName ?: it.callerDisplayName }
+        val msg = IncomingCallMessage(state = state, number = number?.ifBlank { null }, name = name)
+        val sent = wsManager.sendMessage(msg)
+        Log.d(TAG, "Incoming call reported: $state (sent=$sent)")
+    }
+
     private fun reportStatus(state: CallState, number: String? = activeNumber) {
-        val msg = StatusMessage(state = state.raw, number = number)
+        val msg = StatusMessage(state = state.raw, number = number, id = activeCommandId)
         val sent = wsManager.sendMessage(msg)
         Log.d(TAG, "Status reported: ${state.raw} (sent=$sent)")
         scope.launch {
             _callState.value = when (state) {
-                CallState.CALL_STARTED -> CallLifecycle.Active(number ?: "")
+                CallState.CALL_STARTED,
+                CallState.CALL_ANSWERED -> CallLifecycle.Active(number ?: "")
                 CallState.CALL_ENDED   -> CallLifecycle.Idle
                 CallState.CALL_FAILED  -> CallLifecycle.Failed("Call failed")
             }
@@ -101,10 +441,22 @@ class CallManager(
     @Suppress("DEPRECATION")
     private inner class CallStateObserver : PhoneStateListener() {
         private var wasOffHook = false
+        /**
+         * Number of the call ringing while the phone was otherwise idle, until
+         * it is answered or missed.  A call waiting behind an active one only
+         * gets its RINGING reported: the phone stays off-hook either way.
+         */
+        private var ringing: String? = null
 
         override fun onCallStateChanged(state: Int, phoneNumber: String?) {
+            lastTelephonyState = state
+            if (state != TelephonyManager.CALL_STATE_RINGING) ringingNumber = null
             when (state) {
                 TelephonyManager.CALL_STATE_OFFHOOK -> {
+                    ringing?.let {
+                        ringing = null
+                        reportIncoming("ANSWERED", it)
+                    }
                     if (!wasOffHook) {
                         wasOffHook = true
                         Log.d(TAG, "Call state: OFFHOOK")
@@ -112,15 +464,30 @@ class CallManager(
                     }
                 }
                 TelephonyManager.CALL_STATE_IDLE -> {
+                    ringing?.let {
+                        ringing = null
+                        reportIncoming("MISSED", it)
+                    }
                     if (wasOffHook) {
                         wasOffHook = false
                         activeNumber = null
+                        answered = false
+                        legCommands.clear()
                         Log.d(TAG, "Call state: IDLE (after OFFHOOK = call ended)")
                         reportStatus(CallState.CALL_ENDED, null)
+                        activeCommandId = null
                     }
                 }
                 TelephonyManager.CALL_STATE_RINGING -> {
-                    // Incoming call while we placed an outgoing one — edge case, ignore
+                    // With READ_CALL_LOG this fires twice, once without the
+                    // number; it is also empty when the caller withholds it
+                    val number = phoneNumber.orEmpty()
+                    val announced = ringingNumber
+                    if (announced != null && (number.isEmpty() || number == announced)) return
+                    ringingNumber = number
+                    Log.d(TAG, "Call state: RINGING")
+                    if (!wasOffHook) ringing = number
+                    reportIncoming("RINGING", number)
                 }
             }
         }
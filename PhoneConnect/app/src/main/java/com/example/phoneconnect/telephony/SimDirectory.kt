@@ -0,0 +1,75 @@
+package com.example.phoneconnect.telephony
+
+import android.content.Context
+import android.telecom.PhoneAccountHandle
+import android.telecom.TelecomManager
+import android.telephony.SubscriptionInfo
+import android.telephony.SubscriptionManager
+import android.telephony.TelephonyManager
+import android.util.Log
+import com.example.phoneconnect.data.model.SimInfo
+
+private const val TAG = "SimDirectory"
+
+/**
+ * The SIMs in this phone, as reported to the gateway in AUTH (`dial devices`),
+ * and the mapping from a CALL command's `sim` selector to the line to call on.
+ *
+ * A selector is either a 1-based slot number ("1", "2") or a SIM label — the
+ * display name set in Android settings, or failing that the carrier name —
+ * matched case-insensitively.  Requires READ_PHONE_STATE.
+ */
+class SimDirectory(context: Context) {
+    private val subscriptionManager: SubscriptionManager =
+        context.getSystemService(SubscriptionManager::class.java)
+    private val telecomManager: TelecomManager =
+        context.getSystemService(TelecomManager::class.java)
+    private val telephonyManager: TelephonyManager =
+        context.getSystemService(TelephonyManager::class.java)
+
+    // ── Public API ────────────────────────────────────────────────────────────
+
+    /** Active SIMs in slot order; empty without READ_PHONE_STATE. */
+    fun list(): List<SimInfo> = subscriptions().map { sub ->
+        SimInfo(
+            slot = sub.simSlotIndex + 1,
+            label = sub.displayName?.toString()?.ifBlank { null },
+            carrier = sub.carrierName?.toString()?.ifBlank { null },
+        )
+    }
+
+    /**
+     * The phone account that places calls on the SIM picked by [selector],
+     * or null when no active SIM matches it.
+     */
+    fun phoneAccountFor(selector: String): PhoneAccountHandle? {
+        val wanted = selector.trim()
+        val slot = wanted.toIntOrNull()
+        val sub = subscriptions().firstOrNull { sub ->
+            if (slot != null) {
+                sub.simSlotIndex + 1 == slot
+            } else {
+                sub.displayName?.toString().equals(wanted, ignoreCase = true) ||
+                    sub.carrierName?.toString().equals(wanted, ignoreCase = true)
+            }
+        } ?: return null
+
+        return try {
+            telecomManager.callCapablePhoneAccounts.firstOrNull { handle ->
+                telephonyManager.getSubscriptionId(handle) == sub.subscriptionId
+            }
+        } catch (e: SecurityException) {
+            Log.e(TAG, "Permission denied for READ_PHONE_STATE: ${e.message}")
+            null
+        }
+    }
+
+    // ── Helpers ───────────────────────────────────────────────────────────────
+
+    private fun subscriptions(): List<SubscriptionInfo> = try {
+        subscriptionManager.activeSubscriptionInfoList.orEmpty().sortedBy { it.simSlotIndex }
+    } catch (e: SecurityException) {
+        Log.e(TAG, "Permission denied for READ_PHONE_STATE: ${e.message}")
+        emptyList()
+    }
+}
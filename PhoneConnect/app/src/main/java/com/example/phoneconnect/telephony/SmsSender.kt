@@ -0,0 +1,147 @@
+package com.example.phoneconnect.telephony
+
+import android.app.Activity
+import android.app.PendingIntent
+import android.content.BroadcastReceiver
+import android.content.Context
+import android.content.Intent
+import android.content.IntentFilter
+import android.telephony.SmsManager
+import android.util.Log
+import androidx.core.content.ContextCompat
+import com.example.phoneconnect.data.model.SmsState
+import com.example.phoneconnect.data.model.SmsStatusMessage
+import com.example.phoneconnect.network.WsManager
+import java.util.concurrent.ConcurrentHashMap
+
+private const val TAG = "SmsSender"
+
+private const val ACTION_SMS_SENT      = "com.example.phoneconnect.SMS_SENT"
+private const val ACTION_SMS_DELIVERED = "com.example.phoneconnect.SMS_DELIVERED"
+private const val EXTRA_COMMAND_ID     = "commandId"
+
+/**
+ * Sends text messages on behalf of the gateway (`dial sms`) and reports their
+ * progress back as SMS_STATUS messages.
+ *
+ * Flow:
+ *   1. [send] splits the text with [SmsManager.divideMessage] and hands every
+ *      part to the radio with a "sent" and a "delivered" [PendingIntent].
+ *   2. Once every part is sent → SMS_SENT; any failed part → SMS_FAILED.
+ *   3. Once every part is delivered → SMS_DELIVERED.  Carriers that don't
+ *      provide delivery reports simply never trigger this step.
+ */
+class SmsSender(
+    private val context: Context,
+    private val wsManager: WsManager,
+) {
+    private val smsManager: SmsManager = context.getSystemService(SmsManager::class.java)
+
+    /** Per-command progress, keyed by command ID. */
+    private val pending = ConcurrentHashMap<String, Progress>()
+
+    private data class Progress(
+        val parts: Int,
+        var sent: Int = 0,
+        var delivered: Int = 0,
+        var failed: Boolean = false,
+    )
+
+    private val receiver = object : BroadcastReceiver() {
+        override fun onReceive(ctx: Context, intent: Intent) {
+            val commandId = intent.getStringExtra(EXTRA_COMMAND_ID) ?: return
+            val progress = pending[commandId] ?: return
+            when (intent.action) {
+                ACTION_SMS_SENT      -> onPartSent(commandId, progress, resultCode)
+                ACTION_SMS_DELIVERED -> onPartDelivered(commandId, progress)
+            }
+        }
+    }
+
+    init {
+        val filter = IntentFilter().apply {
+            addAction(ACTION_SMS_SENT)
+            addAction(ACTION_SMS_DELIVERED)
+        }
+        ContextCompat.registerReceiver(context, receiver, filter, ContextCompat.RECEIVER_NOT_EXPORTED)
+    }
+
+    // ── Public API ────────────────────────────────────────────────────────────
+
+    /**
+     * Send [text] to [number].  Requires SEND_SMS to be granted at runtime.
+     * [commandId] tags every SMS_STATUS report for this message.
+     */
+    fun send(number: String, text: String, commandId: String) {
+        try {
+            val parts = smsManager.divideMessage(text)
+            pending[commandId] = Progress(parts = parts.size)
+            Log.d(TAG, "Sending SMS to $number in ${parts.size} part(s)")
+
+            val sentIntents = ArrayList(parts.indices.map { pendingIntent(ACTION_SMS_SENT, commandId, it) })
+            val deliveredIntents = ArrayList(parts.indices.map { pendingIntent(ACTION_SMS_DELIVERED, commandId, it) })
+            smsManager.sendMultipartTextMessage(number, null, parts, sentIntents, deliveredIntents)
+        } catch (e: SecurityException) {
+            Log.e(TAG, "Permission denied for SEND_SMS: ${e.message}")
+            fail(commandId, "SEND_SMS permission not granted")
+        } catch (e: Exception) {
+            Log.e(TAG, "Failed to send SMS: ${e.message}")
+            fail(commandId, e.message ?: "Unknown error")
+        }
+    }
+
+    /** Unregister the broadcast receiver.  Call from Service#onDestroy(). */
+    fun destroy() {
+        try { context.unregisterReceiver(receiver) } catch (_: Exception) {}
+    }
+
+    // ── Helpers ───────────────────────────────────────────────────────────────
+
+    private fun pendingIntent(action: String, commandId: String, part: Int): PendingIntent {
+        val intent = Intent(action).setPackage(context.packageName).putExtra(EXTRA_COMMAND_ID, commandId)
+        // Unique request code per (command, part) so the intents don't collapse
+        val requestCode = (commandId.hashCode() * 31) + part * 2 + if (action == ACTION_SMS_SENT) 0 else 1
+        return PendingIntent.getBroadcast(
+            context, requestCode, intent,
+            PendingIntent.FLAG_IMMUTABLE or PendingIntent.FLAG_ONE_SHOT,
+        )
+    }
+
+    private fun onPartSent(commandId: String, progress: Progress, resultCode: Int) {
+        if (progress.failed) return
+        if (resultCode != Activity.RESULT_OK) {
+            progress.failed = true
+            fail(commandId, describeError(resultCode))
+            return
+        }
+        progress.sent++
+        if (progress.sent == progress.parts) report(commandId, SmsState.SMS_SENT)
+    }
+
+    private fun onPartDelivered(commandId: String, progress: Progress) {
+        if (progress.failed) return
+        progress.delivered++
+        if (progress.delivered == progress.parts) {
+            pending.remove(commandId)
+            report(commandId, SmsState.SMS_DELIVERED)
+        }
+    }
+
+    private fun fail(commandId: String, reason: String) {
+        pending.remove(commandId)
+        report(commandId, SmsState.SMS_FAILED, reason)
+    }
+
+    private fun report(commandId: String, state: SmsState, reason: String? = null) {
+        val sent = wsManager.sendMessage(SmsStatusMessage(id = commandId, state = state.raw, reason = reason))
+        Log.d(TAG, "SMS status reported: ${state.raw} (sent=$sent)")
+    }
+
+    private fun describeError(resultCode: Int): String = when (resultCode) {
+        SmsManager.RESULT_ERROR_GENERIC_FAILURE -> "generic failure"
+        SmsManager.RESULT_ERROR_NO_SERVICE      -> "no service"
+        SmsManager.RESULT_ERROR_NULL_PDU        -> "null PDU"
+        SmsManager.RESULT_ERROR_RADIO_OFF       -> "radio off (airplane mode?)"
+        else                                    -> "error code $resultCode"
+    }
+}
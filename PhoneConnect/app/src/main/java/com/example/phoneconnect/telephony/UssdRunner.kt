@@ -0,0 +1,76 @@
+package com.example.phoneconnect.telephony
+
+import android.content.Context
+import android.os.Handler
+import android.os.Looper
+import android.telephony.TelephonyManager
+import android.util.Log
+import com.example.phoneconnect.data.model.UssdResponseMessage
+import com.example.phoneconnect.network.WsManager
+
+private const val TAG = "UssdRunner"
+
+/**
+ * Runs USSD requests on behalf of the gateway (`dial ussd`) and reports the
+ * carrier's reply back as a USSD_RESPONSE message.
+ *
+ * [TelephonyManager.sendUssdRequest] only supports single-shot requests, so
+ * menus are navigated by the CLI sending chained codes (`*123*2#`) — the
+ * form most carriers accept.
+ */
+class UssdRunner(
+    context: Context,
+    private val wsManager: WsManager,
+) {
+    private val telephonyManager: TelephonyManager =
+        context.getSystemService(TelephonyManager::class.java)
+    private val handler = Handler(Looper.getMainLooper())
+
+    // ── Public API ────────────────────────────────────────────────────────────
+
+    /**
+     * Send [code] to the network.  Requires CALL_PHONE to be granted at runtime.
+     * [commandId] tags the USSD_RESPONSE for this request.
+     */
+    fun send(code: String, commandId: String) {
+        Log.d(TAG, "Sending USSD request $code")
+        try {
+            telephonyManager.sendUssdRequest(code, object : TelephonyManager.UssdResponseCallback() {
+                override fun onReceiveUssdResponse(
+                    telephonyManager: TelephonyManager,
+                    request: String,
+                    response: CharSequence,
+                ) {
+                    report(commandId, ok = true, message = response.toString())
+                }
+
+                override fun onReceiveUssdResponseFailed(
+                    telephonyManager: TelephonyManager,
+                    request: String,
+                    failureCode: Int,
+                ) {
+                    report(commandId, ok = false, message = describeFailure(failureCode))
+                }
+            }, handler)
+        } catch (e: SecurityException) {
+            Log.e(TAG, "Permission denied for CALL_PHONE: ${e.message}")
+            report(commandId, ok = false, message = "CALL_PHONE permission not granted")
+        } catch (e: Exception) {
+            Log.e(TAG, "Failed to send USSD request: ${e.message}")
+            report(commandId, ok = false, message = e.message ?: "Unknown error")
+        }
+    }
+
+    // ── Helpers ───────────────────────────────────────────────────────────────
+
+    private fun report(commandId: String, ok: Boolean, message: String) {
+        val sent = wsManager.sendMessage(UssdResponseMessage(id = commandId, ok = ok, message = message))
+        Log.d(TAG, "USSD response reported: ok=$ok (sent=$sent)")
+    }
+
+    private fun describeFailure(failureCode: Int): String = when (failureCode) {
+        TelephonyManager.USSD_RETURN_FAILURE        -> "network rejected the request"
+        TelephonyManager.USSD_ERROR_SERVICE_UNAVAIL -> "USSD service unavailable"
+        else                                        -> "error code $failureCode"
+    }
+}
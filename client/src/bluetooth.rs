@@ -1,34 +1,34 @@
-/// Bluetooth HFP helpers — Linux (PipeWire / PulseAudio) focused.
-///
-/// ## Why the old approach produced silence
-///
-/// `pactl set-card-profile … headset-head-unit` only tells PipeWire that the
-/// device *is capable* of HFP.  It does NOT open the Bluetooth SCO audio
-/// socket.  A Bluetooth SCO socket only opens when PipeWire has an active
-/// audio stream (a "running" node) connected to the HFP sink or source.
-/// Until that happens the nodes stay SUSPENDED and the phone sees dead silence
-/// in both directions.
-///
-/// ## The fix: `HfpSession`
-///
-/// After switching the card profile, we spawn two `pw-loopback` processes:
-///
-///   ① mic-loopback   — captures from `bluez_input.<MAC>` (headset mic /
-///                       phone earpiece output) and plays to the laptop's
-///                       default output.  This activates the SCO inbound path
-///                       so you can *hear* the call.
-///
-///   ② speaker-loopback — captures from the laptop's default microphone and
-///                         plays into `bluez_output.<MAC>` (headset speaker /
-///                         phone earpiece input).  This activates the SCO
-///                         outbound path so the other party can *hear you*.
-///
-/// Both loopbacks run for the duration of the call.  Dropping [`HfpSession`]
-/// kills them cleanly and restores the card to A2DP.
-///
-/// ## Platform scope
-/// On Windows / macOS the OS handles profile-switching and SCO automatically
-/// once the device is set as the Default Communications Device.
+//! Bluetooth HFP helpers — Linux (PipeWire / PulseAudio) focused.
+//!
+//! ## Why the old approach produced silence
+//!
+//! `pactl set-card-profile … headset-head-unit` only tells PipeWire that the
+//! device *is capable* of HFP.  It does NOT open the Bluetooth SCO audio
+//! socket.  A Bluetooth SCO socket only opens when PipeWire has an active
+//! audio stream (a "running" node) connected to the HFP sink or source.
+//! Until that happens the nodes stay SUSPENDED and the phone sees dead silence
+//! in both directions.
+//!
+//! ## The fix: `HfpSession`
+//!
+//! After switching the card profile, we spawn two `pw-loopback` processes:
+//!
+//!   ① mic-loopback   — captures from `bluez_input.<MAC>` (headset mic /
+//!                       phone earpiece output) and plays to the laptop's
+//!                       default output.  This activates the SCO inbound path
+//!                       so you can *hear* the call.
+//!
+//!   ② speaker-loopback — captures from the laptop's default microphone and
+//!                         plays into `bluez_output.<MAC>` (headset speaker /
+//!                         phone earpiece input).  This activates the SCO
+//!                         outbound path so the other party can *hear you*.
+//!
+//! Both loopbacks run for the duration of the call.  Dropping [`HfpSession`]
+//! kills them cleanly and restores the card to A2DP.
+//!
+//! ## Platform scope
+//! On Windows / macOS the OS handles profile-switching and SCO automatically
+//! once the device is set as the Default Communications Device.
 
 // ── Types ─────────────────────────────────────────────────────────────────────
 
@@ -376,10 +376,8 @@ mod inner {
             if try_set(card_name, cvsd) { return Ok(HfpCodec::Cvsd); }
         }
 
-        if has_gateway {
-            if try_set(card_name, "audio-gateway") {
-                return Ok(HfpCodec::PhoneGateway);
-            }
+        if has_gateway && try_set(card_name, "audio-gateway") {
+            return Ok(HfpCodec::PhoneGateway);
         }
 
         Err(format!(
@@ -399,8 +397,10 @@ mod inner {
             "a2dp-sink-sbc",
         ];
         for p in &candidates {
-            if profiles.is_empty() || profiles.iter().any(|ap| ap == *p) {
-                if try_set(card_name, p) { return Ok(()); }
+            if (profiles.is_empty() || profiles.iter().any(|ap| ap == *p))
+                && try_set(card_name, p)
+            {
+                return Ok(());
             }
         }
         Err(format!(
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::bluetooth::mac_to_card_name;
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+use crate::session;
+
+use super::{command_options, hold_hfp, open_hfp, resolve_device, show_receipt, show_unconfirmed};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct AnswerArgs {
+    /// Device the call is ringing on.  May be omitted when only one device
+    /// is connected.
+    device_id: Option<String>,
+
+    /// Bluetooth MAC of your phone (e.g. AA:BB:CC:DD:EE:FF).  Defaults to
+    /// `bt_mac` in config; when set, call audio moves to your laptop's
+    /// headset as soon as the call is answered.
+    #[arg(long, value_name = "MAC")]
+    bt_mac: Option<String>,
+
+    /// Seconds to wait for the device to confirm (0 = don't wait, in which
+    /// case `dial` can't tell whether a call was ringing).  Defaults to
+    /// `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+// ── dial answer [device_id] ───────────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: AnswerArgs) -> Result<(), DialError> {
+    let AnswerArgs { device_id, bt_mac, ack_timeout } = args;
+    let device_id = resolve_device(gateway, device_id).await?;
+
+    println!("{} Answering the call on {}", "→".cyan().bold(), device_id.yellow());
+
+    let opts = command_options(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+    let mut receipt = gateway.answer(&device_id, &opts).await?;
+
+    match receipt.result.take() {
+        Some(outcome) if !outcome.ringing => {
+            return Err(DialError::NothingRinging { device_id: receipt.device_id });
+        }
+        Some(outcome) if !outcome.answered => {
+            return Err(DialError::AnswerFailed {
+                device_id: receipt.device_id,
+                reason: outcome.reason.unwrap_or_else(|| "unknown reason".to_string()),
+            });
+        }
+        Some(outcome) => match outcome.number {
+            Some(number) => println!("{} Answered the call from {}", "✓".green().bold(), number.yellow()),
+            None => println!("{} Answered the call", "✓".green().bold()),
+        },
+        None => show_unconfirmed(&receipt, "Answer", "say whether a call was ringing"),
+    }
+    show_receipt(&receipt);
+
+    // ── Move the conversation to the laptop, as `dial call` does ─────────
+    let bt_card_name = bt_mac.or_else(|| config.bt_mac.clone()).as_deref().map(mac_to_card_name);
+    let Some(_hfp_session) = bt_card_name.as_deref().and_then(open_hfp) else {
+        return Ok(());
+    };
+    // Lets `dial hangup` close this session from another terminal
+    let _session_lock = session::register(&receipt.device_id);
+    hold_hfp(None).await;
+    // HfpSession::drop() fires here — kills loopbacks, restores A2DP.
+    Ok(())
+}
@@ -0,0 +1,634 @@
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use tokio::sync::Notify;
+
+use crate::api::{validate_dtmf, validate_sim, CallControlState, CallSpec, CommandOptions, ControlAction};
+use crate::bluetooth::mac_to_card_name;
+use crate::config::Config;
+use crate::contacts::is_name;
+use crate::dialstring::{DialString, PostDial, PAUSE};
+use crate::errors::DialError;
+use crate::events::{CallState, EventStream, GatewayEvent};
+use crate::gateway::Gateway;
+use crate::history;
+use crate::keypad::Keypad;
+use crate::session::{self, Stop};
+
+use super::{
+    check_sim, command_options, contacts, extract, hold_hfp, open_hfp, picker, resolve_device, show_normalized,
+    show_receipt,
+};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct CallArgs {
+    /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb).
+    /// May be left out: `dial call +14155550100` calls from the only
+    /// connected device, or asks which one.
+    device_id: Option<String>,
+
+    /// Phone number in E.164 format (e.g. +919876543210) or a name from
+    /// `dial contacts`, optionally followed by keys to press once
+    /// connected: `,` pauses 2 s, `;` waits for Enter (e.g.
+    /// +14155550100,,,1234#).  Omit to pick one from recent calls and
+    /// contacts in a fuzzy finder.
+    number: Option<String>,
+
+    /// Bluetooth MAC of your phone (e.g. AA:BB:CC:DD:EE:FF).
+    /// When supplied, the BT card is automatically switched to HFP before
+    /// the call so audio routes to your laptop speakers/mic.
+    #[arg(long, value_name = "MAC")]
+    bt_mac: Option<String>,
+
+    /// Line to call from on a dual-SIM phone: slot number (1, 2) or the
+    /// SIM's label as listed by `dial devices`.  Defaults to the number's
+    /// `[sim_by_number]` entry in config, then `default_sim`.
+    #[arg(long, value_name = "SLOT|LABEL")]
+    sim: Option<String>,
+
+    /// Withhold caller ID for this call.  The device dials its carrier's
+    /// code in front of the number (`[caller_id_prefix]` in config, or
+    /// #31#).  Implied for numbers matching `private_numbers` in config.
+    #[arg(long, overrides_with = "no_private")]
+    private: bool,
+
+    /// Show caller ID even if the number matches `private_numbers`
+    #[arg(long, overrides_with = "private")]
+    no_private: bool,
+
+    /// Stay attached and print the call's lifecycle (started / ended /
+    /// failed) as the device reports it.  Exit code reflects the outcome:
+    /// 0 ended normally, 3 failed, 4 device dropped, 5 outcome unknown.
+    /// Keys typed meanwhile (0-9 * # A-D) are sent as DTMF tones; m
+    /// toggles mute, s the speakerphone and h hold.
+    #[arg(long)]
+    follow: bool,
+
+    /// Call the phone number found in text read from stdin (an email
+    /// signature, a web page), e.g. `xclip -o | dial call <device>
+    /// --from-stdin`.  Several numbers get a picker on the terminal.
+    #[arg(long, conflicts_with = "number")]
+    from_stdin: bool,
+
+    /// Seconds to wait for the device to acknowledge the command
+    /// (0 = don't wait).  Defaults to `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+
+    /// Reuse the idempotency key printed by an earlier attempt so that
+    /// re-running a failed `dial call` can never ring the number twice.
+    /// Defaults to a fresh key.
+    #[arg(long, value_name = "KEY")]
+    idempotency_key: Option<String>,
+}
+
+impl CallArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        if self.device_id.as_deref().is_some_and(|id| id.trim().is_empty()) {
+            return Err(DialError::EmptyDeviceId);
+        }
+        if let Some(sim) = &self.sim {
+            validate_sim(sim)?;
+        }
+        // A lone number lands in `device_id`; `run` moves it over
+        let number = match (self.device_id.as_ref(), self.number.as_ref()) {
+            (Some(id), None) if is_number(id) && !self.from_stdin => Some(id),
+            (Some(id), _) if is_number(id) => return Err(DialError::NumberAsDevice(id.clone())),
+            (_, number) => number,
+        };
+        match number {
+            None if self.from_stdin => Ok(()),
+            None if !std::io::stdin().is_terminal() => Err(DialError::NoNumber),
+            None => Ok(()),
+            // The region only comes with the config; the number is read in
+            // it later, and names are looked up once the contacts are loaded
+            Some(number) => match DialString::check(number) {
+                Err(_) if is_name(number) => Ok(()),
+                checked => checked,
+            },
+        }
+    }
+}
+
+// ── dial call [device_id] [number] [--bt-mac MAC] [--follow] ─────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: CallArgs) -> Result<(), DialError> {
+    let CallArgs {
+        device_id,
+        number,
+        bt_mac,
+        sim,
+        private,
+        no_private,
+        follow,
+        from_stdin,
+        ack_timeout,
+        idempotency_key,
+    } = args;
+
+    // clap fills `device_id` first, so `dial call +14155550100` puts the
+    // number there (`validate` made sure nothing else was given)
+    let (device_id, number) = match (device_id, number) {
+        (Some(id), None) if is_number(&id) => (None, Some(id)),
+        given => given,
+    };
+
+    // ── No number: take it from stdin, or pick one (and the device) ──
+    let picked = match (number, from_stdin) {
+        (Some(number), _) => device_or_pick(gateway, device_id).await?.map(|id| (id, number)),
+        (None, true) => match extract::number_from_stdin(config.region()).await? {
+            Some(number) => device_or_pick(gateway, device_id).await?.map(|id| (id, number)),
+            None => None,
+        },
+        (None, false) => picker::pick(gateway, device_id).await?,
+    };
+    let Some((device_id, number)) = picked else {
+        println!("{} Nothing picked — no call made", "○".dimmed());
+        return Ok(());
+    };
+
+    // ── Not a number: a contact's name, maybe with keys to press after it ─
+    let dial = match DialString::parse(&number, config.region()) {
+        Ok(dial) => dial,
+        Err(not_a_number) => {
+            let number = match contact_number(&number).await {
+                Err(DialError::UnknownContact(_)) if !is_name(&number) => return Err(not_a_number),
+                found => found?,
+            };
+            DialString::parse(&number, config.region())?
+        }
+    };
+    let number = dial.number.clone();
+
+    // ── Resolve SIM: --sim, then the number's fixed line, then default ──
+    let sim = sim.or_else(|| config.sim_for(&number).map(str::to_string));
+    let line = match &sim {
+        Some(sim) => Some(check_sim(gateway, &device_id, sim).await?),
+        None => None,
+    };
+    let withhold_caller_id = private || (!no_private && config.is_private_number(&number));
+    let caller_id_prefix = config.caller_id_prefix_for(&device_id).map(str::to_string);
+
+    // ── Resolve BT MAC: CLI flag takes precedence, then config fallback ─────
+    let bt_card_name = bt_mac.or_else(|| config.bt_mac.clone()).as_deref().map(mac_to_card_name);
+
+    // ── Optional: auto-switch BT to HFP + open SCO socket ───────────
+    // The session is kept alive until the call command returns.
+    let _hfp_session = bt_card_name.as_deref().and_then(open_hfp);
+    // Lets `dial hangup` close this session from another terminal
+    let _session_lock = _hfp_session.as_ref().and_then(|_| session::register(&device_id));
+
+    // ── Optional: subscribe to STATUS updates before dispatching ─────
+    // Subscribing first guarantees no transition is missed, however
+    // quickly the device reports back.  Post-dial keys need to know when
+    // the call is answered, so they imply following until they're sent.
+    let mut events = if follow || !dial.post_dial.is_empty() {
+        Some(gateway.events(Some(&device_id)).await?)
+    } else {
+        None
+    };
+
+    let idempotency_key = idempotency_key.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    show_normalized(&dial.typed, &number, "dialing");
+    println!(
+        "{} Dispatching call to {} → {}",
+        "→".cyan().bold(),
+        device_id.yellow(),
+        number.yellow()
+    );
+    if let Some(steps) = dial.describe_post_dial() {
+        println!("  Dial   : {}  {}", dial.to_string().cyan(), format!("(then {steps})").dimmed());
+    }
+    if let Some(line) = &line {
+        println!("  Line   : {}", line.cyan());
+    }
+    if withhold_caller_id {
+        let via = caller_id_prefix.as_deref().unwrap_or("#31#");
+        println!("  Caller : {}  {}", "withheld".cyan(), format!("(via {via})").dimmed());
+    }
+    // Printed up front so it is on screen even if dispatch fails
+    println!("  Key    : {}", idempotency_key.dimmed());
+
+    let opts = CommandOptions {
+        ack_timeout: Some(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs)),
+        idempotency_key: Some(idempotency_key),
+    };
+    let spec = CallSpec { number: number.clone(), sim, withhold_caller_id, caller_id_prefix };
+    let result = match gateway.call(&device_id, &spec, &opts).await {
+        Ok(r) => r,
+        Err(e) => {
+            if matches!(e, DialError::RateLimited { .. }) && !config.retry_policy().wait_on_rate_limit {
+                eprintln!(
+                    "  Re-run with {} to wait for the limit to reset automatically.",
+                    "--wait-on-rate-limit".cyan()
+                );
+            }
+            if e.is_transient() {
+                eprintln!(
+                    "  Re-run with {} to retry without risking a duplicate call.",
+                    format!("--idempotency-key {}", opts.idempotency_key.as_deref().unwrap_or("")).cyan()
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    if result.delivered {
+        println!("{} Call command delivered — device acknowledged", "✓".green().bold());
+    } else {
+        println!("{} Call command sent (delivery not confirmed)", "✓".green().bold());
+    }
+    show_receipt(&result);
+    history::record(&result.device_id, &number);
+
+    // ── --follow: block until the call ends; HFP is restored on return ─
+    // Without --follow, only until the post-dial keys have been sent.
+    let mut stopped = false;
+    // Keys typed while following become DTMF (IVR menus, PINs)
+    let mut keypad = events.as_ref().and_then(|_| Keypad::open());
+    let interactive = keypad.is_some();
+    if let Some(ref mut stream) = events {
+        println!();
+        if interactive {
+            println!(
+                "{} Type 0-9 * # A-D to send DTMF tones  {}",
+                "♪".cyan(),
+                "(m mute · s speaker · h hold)".dimmed()
+            );
+        }
+        let answered = Notify::new();
+        let resume = Notify::new();
+        let watch = async {
+            match keypad.as_mut() {
+                Some(keys) => {
+                    let ids = (result.device_id.as_str(), result.command_id.as_str());
+                    follow_interactive(gateway, config, stream, keys, ids, &number, &answered, &resume).await
+                }
+                None => follow_call(stream, &result.device_id, &result.command_id, &number, &answered).await,
+            }
+        };
+        let attended = async {
+            if dial.post_dial.is_empty() {
+                return watch.await;
+            }
+            let player = async {
+                answered.notified().await;
+                let resume = interactive.then_some(&resume);
+                play_post_dial(gateway, config, &result.device_id, &dial.post_dial, resume).await
+            };
+            tokio::pin!(watch);
+            tokio::select! {
+                outcome = &mut watch => {
+                    outcome?;
+                    return Err(DialError::PostDialNotSent {
+                        number: number.clone(),
+                        keys: dial.describe_post_dial().unwrap_or_default(),
+                    });
+                }
+                played = player => played?,
+            }
+            if follow { watch.await } else { Ok(()) }
+        };
+        if _hfp_session.is_some() || interactive {
+            // Return on Ctrl-C / `dial hangup` instead of dying so the HFP
+            // session and the keypad can restore audio and the terminal
+            tokio::select! {
+                outcome = attended => outcome?,
+                stop = session::terminated() => {
+                    stopped = true;
+                    match (stop, _hfp_session.is_some()) {
+                        (Stop::HungUp, true) => {
+                            println!("  {} HFP session closed by `dial hangup`", "■".cyan());
+                        }
+                        (Stop::Interrupted, true) => println!(
+                            "  {} Interrupted — HFP session closed, the call continues on the phone",
+                            "■".cyan()
+                        ),
+                        (_, false) => println!("  {} Stopped watching — the call continues", "■".cyan()),
+                    }
+                }
+            }
+        } else {
+            attended.await?;
+        }
+        if follow && _hfp_session.is_some() {
+            println!("  {} Restoring A2DP stereo…", "♫".cyan());
+        }
+    }
+    // ── Remind the user how to restore audio after the call ───────────
+    if !follow && !stopped && _hfp_session.is_some() {
+        // Post-dial keys left the keypad reading stdin
+        hold_hfp(keypad.as_mut()).await;
+        // HfpSession::drop() fires here — kills loopbacks, restores A2DP.
+    }
+    Ok(())
+}
+
+// ── Call following ─────────────────────────────────────────────────────────────
+
+/// Print each lifecycle transition of the call identified by `command_id`
+/// until it reaches a final state.
+///
+/// Returns `Ok` when the call ends normally and an error (with its own exit
+/// code) when it fails, the device drops, or the gateway closes the stream.
+async fn follow_call(
+    events: &mut EventStream,
+    device_id: &str,
+    command_id: &str,
+    number: &str,
+    answered_signal: &Notify,
+) -> Result<(), DialError> {
+    let dispatched = Instant::now();
+    let mut started = false;
+    println!("{} Following call — Ctrl-C stops watching (the call continues)", "◎".cyan());
+
+    while let Some(event) = events.next().await? {
+        match event {
+            GatewayEvent::Status { state, command_id: event_cmd, .. } => {
+                match event_cmd.as_deref() {
+                    // Update for a different command on the same device
+                    Some(id) if id != command_id => continue,
+                    // Older app versions don't tag STATUS with the command id;
+                    // a lone CALL_ENDED then belongs to an earlier call.
+                    None if state == CallState::Ended && !started => continue,
+                    _ => {}
+                }
+
+                let (icon, label) = match state {
+                    CallState::Started  => ("☎".green().bold(), state.label().green()),
+                    CallState::Answered => ("✓".green().bold(), state.label().green()),
+                    CallState::Ended    => ("■".cyan().bold(), state.label().cyan()),
+                    CallState::Failed   => ("✗".red().bold(), state.label().red()),
+                };
+                println!(
+                    "  {}  {} {:<13} {}",
+                    chrono::Local::now().format("%H:%M:%S").to_string().dimmed(),
+                    icon,
+                    label,
+                    format_elapsed(dispatched.elapsed()).dimmed(),
+                );
+
+                match state {
+                    CallState::Started  => started = true,
+                    // The far end picked up: post-dial keys can go now
+                    CallState::Answered => {
+                        started = true;
+                        answered_signal.notify_one();
+                    }
+                    CallState::Ended    => return Ok(()),
+                    CallState::Failed   => {
+                        return Err(DialError::CallFailed { number: number.to_string() })
+                    }
+                }
+            }
+            GatewayEvent::Disconnected { reason } => {
+                println!(
+                    "  {}  {} {:<13} {}",
+                    chrono::Local::now().format("%H:%M:%S").to_string().dimmed(),
+                    "✗".red().bold(),
+                    "DISCONNECTED".red(),
+                    reason.unwrap_or_default().dimmed(),
+                );
+                return Err(DialError::DeviceDisconnected { device_id: device_id.to_string() });
+            }
+            GatewayEvent::SmsStatus { .. }
+            | GatewayEvent::UssdResponse { .. }
+            | GatewayEvent::LegStatus { .. }
+            | GatewayEvent::Incoming { .. }
+            | GatewayEvent::Unknown => {}
+        }
+    }
+
+    Err(DialError::EventStreamClosed)
+}
+
+/// [`follow_call`], forwarding every DTMF key typed on `keys` to the device
+/// while the call is being followed.  Enter wakes `resume` (for `;` waits);
+/// m, s and h mute, switch the speakerphone and hold.  A key the call won't
+/// take is only noted on the timeline.
+#[allow(clippy::too_many_arguments)]
+async fn follow_interactive(
+    gateway: &dyn Gateway,
+    config: &Config,
+    events: &mut EventStream,
+    keys: &mut Keypad,
+    (device_id, command_id): (&str, &str),
+    number: &str,
+    answered: &Notify,
+    resume: &Notify,
+) -> Result<(), DialError> {
+    let follow = follow_call(events, device_id, command_id, number, answered);
+    tokio::pin!(follow);
+    // Toggles flip whatever the device last reported
+    let mut state = CallControlState::default();
+    loop {
+        tokio::select! {
+            outcome = &mut follow => return outcome,
+            Some(key) = keys.next() => {
+                if key == '\n' {
+                    resume.notify_one();
+                    continue;
+                }
+                let action = match key {
+                    'm' | 'M' if state.muted => Some(ControlAction::Unmute),
+                    'm' | 'M' => Some(ControlAction::Mute),
+                    's' | 'S' => Some(ControlAction::Speaker),
+                    'h' | 'H' if state.on_hold => Some(ControlAction::Resume),
+                    'h' | 'H' => Some(ControlAction::Hold),
+                    _ => None,
+                };
+                if let Some(action) = action {
+                    send_control(gateway, config, device_id, action, &mut state).await;
+                    continue;
+                }
+                let key = key.to_ascii_uppercase().to_string();
+                if validate_dtmf(&key).is_ok() {
+                    let _ = send_tones(gateway, config, device_id, &key).await;
+                }
+            }
+        }
+    }
+}
+
+/// Play the post-dial part of a dial string into the answered call.
+/// `resume` is woken by Enter; without a terminal a `;` wait is a pause.
+/// Stops at the first keys the device doesn't play.
+async fn play_post_dial(
+    gateway: &dyn Gateway,
+    config: &Config,
+    device_id: &str,
+    steps: &[PostDial],
+    resume: Option<&Notify>,
+) -> Result<(), DialError> {
+    for step in steps {
+        match step {
+            PostDial::Pause => tokio::time::sleep(PAUSE).await,
+            PostDial::Wait => match resume {
+                Some(resume) => {
+                    println!("  {} Press {} to send the next keys", "⏸".cyan(), "Enter".cyan());
+                    resume.notified().await;
+                }
+                None => {
+                    eprintln!("{} No terminal to wait on — pausing instead", "warn:".yellow());
+                    tokio::time::sleep(PAUSE).await;
+                }
+            },
+            PostDial::Tones(keys) => send_tones(gateway, config, device_id, keys).await?,
+        }
+    }
+    Ok(())
+}
+
+/// Send DTMF keys and print the outcome on the timeline.  Keys the device
+/// doesn't play are an error.
+async fn send_tones(gateway: &dyn Gateway, config: &Config, device_id: &str, key: &str) -> Result<(), DialError> {
+    let opts = command_options(config.ack_timeout());
+    let now = chrono::Local::now().format("%H:%M:%S").to_string();
+    let receipt = match gateway.dtmf(device_id, key, &opts).await {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            println!("  {}  {} {}", now.dimmed(), "✗".red(), format!("DTMF {key}").red());
+            return Err(e);
+        }
+    };
+    match receipt.result {
+        Some(outcome) if !outcome.played => {
+            let reason = outcome.reason.unwrap_or_else(|| "unknown reason".to_string());
+            println!(
+                "  {}  {} {:<13} {}",
+                now.dimmed(),
+                "✗".red(),
+                format!("DTMF {key}").red(),
+                reason.dimmed(),
+            );
+            Err(DialError::DtmfNotPlayed { device_id: receipt.device_id, reason })
+        }
+        _ => {
+            println!("  {}  {} {}", now.dimmed(), "♪".cyan(), format!("DTMF {key}").cyan());
+            Ok(())
+        }
+    }
+}
+
+/// Run an in-call control bound to a key and print the state the device
+/// reports on the timeline.  Failures are only reported — the call goes on.
+async fn send_control(
+    gateway: &dyn Gateway,
+    config: &Config,
+    device_id: &str,
+    action: ControlAction,
+    state: &mut CallControlState,
+) {
+    let opts = command_options(config.ack_timeout());
+    let now = chrono::Local::now().format("%H:%M:%S").to_string();
+    let label = action.to_string().to_uppercase();
+    match gateway.control(device_id, action, &opts).await {
+        Ok(receipt) => match receipt.result {
+            Some(outcome) if !outcome.applied => println!(
+                "  {}  {} {:<13} {}",
+                now.dimmed(),
+                "⚙".yellow(),
+                label.yellow(),
+                outcome.reason.unwrap_or_default().dimmed(),
+            ),
+            Some(outcome) => {
+                if let Some(reported) = outcome.state {
+                    *state = reported;
+                }
+                println!("  {}  {} {:<13} {}", now.dimmed(), "⚙".cyan(), label.cyan(), state.to_string().dimmed());
+            }
+            None => println!("  {}  {} {}", now.dimmed(), "⚙".cyan(), label.cyan()),
+        },
+        Err(e) => eprintln!("  {}  {} {label} failed: {e}", now.dimmed(), "warn:".yellow()),
+    }
+}
+
+/// `+4.2s` / `+3m07s` / `+1h02m` relative to dispatch.
+fn format_elapsed(d: Duration) -> String {
+    let secs = d.as_secs();
+    match secs {
+        0..=59    => format!("+{:.1}s", d.as_secs_f64()),
+        60..=3599 => format!("+{}m{:02}s", secs / 60, secs % 60),
+        _         => format!("+{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+/// The number to dial for the contact `input` names.  Only if no contact
+/// has the whole of `input` as its name (`Smith, John`) is it read as a
+/// name followed by keys to press once connected (`alice,,12`).
+async fn contact_number(input: &str) -> Result<String, DialError> {
+    let ((contact, entry), post_dial) = match contacts::resolve(input).await {
+        Err(DialError::UnknownContact(_)) if input.contains([',', ';']) => {
+            let (name, post_dial) = input.split_at(input.find([',', ';']).unwrap_or(input.len()));
+            (contacts::resolve(name).await?, post_dial)
+        }
+        found => (found?, ""),
+    };
+    match &entry.label {
+        Some(label) => println!("{} {} ({label}) → dialing {}", "◎".cyan(), contact.cyan(), entry.number.yellow()),
+        None => println!("{} {} → dialing {}", "◎".cyan(), contact.cyan(), entry.number.yellow()),
+    }
+    Ok(format!("{}{post_dial}", entry.number))
+}
+
+/// `device_id`, else the only connected device or, with a terminal to ask
+/// on, the one the user picks.  `None` when the user backs out.
+async fn device_or_pick(gateway: &dyn Gateway, device_id: Option<String>) -> Result<Option<String>, DialError> {
+    match device_id {
+        Some(id) => Ok(Some(id)),
+        // stdin may carry the number; the picker draws on stderr
+        None if std::io::stderr().is_terminal() => picker::pick_device(gateway, None).await,
+        None => resolve_device(gateway, None).await.map(Some),
+    }
+}
+
+/// Whether a positional argument spells a number (`+1 415…`, `(415) …`,
+/// `0207…`) rather than a device ID, which starts with a letter.
+fn is_number(arg: &str) -> bool {
+    arg.trim_start().starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '('))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: CallArgs,
+    }
+
+    fn validate(argv: &[&str]) -> Result<(), DialError> {
+        Cli::try_parse_from(["dial"].iter().chain(argv)).unwrap().args.validate()
+    }
+
+    #[test]
+    fn a_lone_number_is_the_number() {
+        assert!(validate(&["+14155550100"]).is_ok());
+        assert!(validate(&["(415) 555-0100,,12"]).is_ok());
+        assert!(validate(&["android_abc", "+14155550100"]).is_ok());
+        let err = validate(&["+1 415 CALL"]).unwrap_err();
+        assert!(matches!(err, DialError::InvalidPhoneNumber(_)), "{err}");
+    }
+
+    #[test]
+    fn a_number_in_place_of_the_device_is_refused() {
+        for argv in [&["+14155550100", "+14155550101"][..], &["+14155550100", "--from-stdin"]] {
+            let err = validate(argv).unwrap_err();
+            assert!(matches!(err, DialError::NumberAsDevice(ref n) if n == "+14155550100"), "{argv:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn options_are_checked_for_a_lone_number_too() {
+        let err = validate(&["+14155550100", "--sim", "0"]).unwrap_err();
+        assert!(matches!(err, DialError::InvalidSim(_)), "{err}");
+    }
+}
@@ -0,0 +1,294 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::api::{validate_sim, CallSpec};
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::events::{CallState, EventStream, GatewayEvent, LegState};
+use crate::gateway::Gateway;
+use crate::phone;
+use crate::session;
+
+use super::{check_sim, command_options, show_normalized};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct ConferenceArgs {
+    /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb)
+    device_id: String,
+
+    /// Party to add to the call in progress, in E.164 format
+    /// (e.g. +919876543210) or as written locally when `default_region` is
+    /// set.  Omit to merge the calls already up (after an earlier
+    /// `--no-merge`, or a call added on the phone).
+    number: Option<String>,
+
+    /// Line to call the new party from (as for `dial call --sim`)
+    #[arg(long, value_name = "SLOT|LABEL")]
+    sim: Option<String>,
+
+    /// Seconds to wait for the new party to answer before giving up on the
+    /// merge.  Their call keeps ringing; the first call stays on hold.
+    #[arg(long, value_name = "SECS", default_value_t = 60)]
+    answer_timeout: u64,
+
+    /// Only add the second call — the first stays on hold, unmerged
+    #[arg(long)]
+    no_merge: bool,
+
+    /// Stay attached after merging and print each leg's state until the
+    /// conference ends
+    #[arg(long)]
+    follow: bool,
+
+    /// Seconds to wait for the device to acknowledge each command.
+    /// Defaults to `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+impl ConferenceArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        if self.device_id.trim().is_empty() {
+            return Err(DialError::EmptyDeviceId);
+        }
+        if let Some(sim) = &self.sim {
+            validate_sim(sim)?;
+        }
+        match &self.number {
+            Some(number) => phone::check(number),
+            None => Ok(()),
+        }
+    }
+}
+
+// ── dial conference <device_id> [number] ─────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: ConferenceArgs) -> Result<(), DialError> {
+    let ConferenceArgs { device_id, number, sim, answer_timeout, no_merge, follow, ack_timeout } = args;
+    let ack_timeout = ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs);
+    let opts = || command_options(ack_timeout);
+
+    // Subscribe first so not a single leg update is missed
+    let mut events = gateway.events(Some(&device_id)).await?;
+    let mut legs = Legs::default();
+
+    match number {
+        Some(number) => {
+            add_party(gateway, config, &mut events, &mut legs, &device_id, number, sim, answer_timeout).await?;
+            if no_merge {
+                println!(
+                    "\n{} Both calls are up — merge them later with `dial conference {device_id}`",
+                    "✓".green().bold()
+                );
+                return Ok(());
+            }
+        }
+        None => println!("{} Merging the calls on {}", "→".cyan().bold(), device_id.yellow()),
+    }
+
+    // ── Merge ────────────────────────────────────────────────────────
+    let merged = gateway.merge(&device_id, &opts()).await?;
+    match merged.result {
+        Some(outcome) if !outcome.merged => {
+            return Err(DialError::MergeFailed {
+                device_id: merged.device_id,
+                reason: outcome.reason.unwrap_or_else(|| "unknown reason".to_string()),
+            });
+        }
+        Some(_) => println!("  {}  {} {}", now().dimmed(), "⚭".green().bold(), "MERGED".green()),
+        None => println!("  {}  {} {}", now().dimmed(), "⚭".cyan(), "MERGE SENT".cyan()),
+    }
+
+    if !follow {
+        return Ok(());
+    }
+    tokio::select! {
+        outcome = watch_legs(&mut events, &mut legs, &device_id) => outcome,
+        _ = session::terminated() => {
+            println!("  {} Stopped watching — the conference continues", "○".dimmed());
+            Ok(())
+        }
+    }
+}
+
+/// Call `number` while the first call is up and wait for them to answer.
+#[allow(clippy::too_many_arguments)]
+async fn add_party(
+    gateway: &dyn Gateway,
+    config: &Config,
+    events: &mut EventStream,
+    legs: &mut Legs,
+    device_id: &str,
+    typed: String,
+    sim: Option<String>,
+    answer_timeout: u64,
+) -> Result<(), DialError> {
+    let number = config.normalize(&typed)?;
+    let sim = sim.or_else(|| config.sim_for(&number).map(str::to_string));
+    let line = match &sim {
+        Some(sim) => Some(check_sim(gateway, device_id, sim).await?),
+        None => None,
+    };
+
+    show_normalized(&typed, &number, "dialing");
+    println!(
+        "{} Adding {} to the call on {}",
+        "→".cyan().bold(),
+        number.yellow(),
+        device_id.yellow()
+    );
+    if let Some(line) = &line {
+        println!("  Line   : {}", line.cyan());
+    }
+
+    let opts = command_options(config.ack_timeout());
+    let spec = CallSpec {
+        number: number.clone(),
+        sim,
+        withhold_caller_id: config.is_private_number(&number),
+        caller_id_prefix: config.caller_id_prefix_for(device_id).map(str::to_string),
+    };
+    let receipt = gateway.call(device_id, &spec, &opts).await?;
+    println!("  Command: {}", receipt.command_id.dimmed());
+    println!();
+
+    let answered = tokio::time::timeout(
+        Duration::from_secs(answer_timeout),
+        await_answer(events, legs, device_id, &receipt.command_id, &number),
+    );
+    match answered.await {
+        Ok(outcome) => outcome,
+        Err(_) => Err(DialError::LegNotAnswered { number, timeout_secs: answer_timeout }),
+    }
+}
+
+// ── Leg tracking ──────────────────────────────────────────────────────────────
+
+/// One call of the conference, as last reported by the device.
+struct Leg {
+    number: String,
+    state: LegState,
+    conference: bool,
+}
+
+/// Every leg seen so far, numbered in order of appearance.
+#[derive(Default)]
+struct Legs(Vec<Leg>);
+
+impl Legs {
+    /// Record an update and print it if anything changed.
+    fn update(&mut self, number: &str, state: LegState, conference: bool) {
+        let index = match self.0.iter().position(|leg| leg.number == number) {
+            Some(i) => {
+                let leg = &mut self.0[i];
+                if leg.state == state && leg.conference == conference {
+                    return;
+                }
+                leg.state = state;
+                leg.conference = conference;
+                i
+            }
+            None => {
+                self.0.push(Leg { number: number.to_string(), state, conference });
+                self.0.len() - 1
+            }
+        };
+
+        let leg = &self.0[index];
+        let (icon, label) = match leg.state {
+            LegState::Dialing | LegState::Ringing => ("◌".cyan(), leg.state.label().cyan()),
+            LegState::Active => ("☎".green().bold(), leg.state.label().green()),
+            LegState::Holding => ("⏸".yellow(), leg.state.label().yellow()),
+            LegState::Disconnected => ("■".cyan().bold(), leg.state.label().cyan()),
+        };
+        println!(
+            "  {}  {} {:<13} leg {} · {}{}",
+            now().dimmed(),
+            icon,
+            label,
+            index + 1,
+            leg.number,
+            if leg.conference { "  (in conference)".dimmed() } else { "".dimmed() },
+        );
+    }
+
+    fn all_disconnected(&self) -> bool {
+        self.0.iter().all(|leg| leg.state == LegState::Disconnected)
+    }
+}
+
+/// Print leg updates until the call placed by `command_id` is answered.
+async fn await_answer(
+    events: &mut EventStream,
+    legs: &mut Legs,
+    device_id: &str,
+    command_id: &str,
+    number: &str,
+) -> Result<(), DialError> {
+    while let Some(event) = events.next().await? {
+        match event {
+            GatewayEvent::LegStatus { number: leg, state, conference, command_id: event_cmd } => {
+                legs.update(&leg, state, conference);
+                // Untagged legs (no command id) are matched by number
+                let ours = event_cmd.map_or_else(|| leg.ends_with(number), |id| id == command_id);
+                match state {
+                    LegState::Active if ours => return Ok(()),
+                    LegState::Disconnected if ours => {
+                        return Err(DialError::CallFailed { number: number.to_string() });
+                    }
+                    _ => {}
+                }
+            }
+            GatewayEvent::Status { state: CallState::Failed, command_id: Some(id) } if id == command_id => {
+                println!("  {}  {} {}", now().dimmed(), "✗".red().bold(), CallState::Failed.label().red());
+                return Err(DialError::CallFailed { number: number.to_string() });
+            }
+            // The phone went idle: every call, including the first, is over
+            GatewayEvent::Status { state: CallState::Ended, .. } => {
+                return Err(DialError::CallFailed { number: number.to_string() });
+            }
+            GatewayEvent::Disconnected { .. } => {
+                return Err(DialError::DeviceDisconnected { device_id: device_id.to_string() });
+            }
+            GatewayEvent::Status { .. }
+            | GatewayEvent::SmsStatus { .. }
+            | GatewayEvent::UssdResponse { .. }
+            | GatewayEvent::Incoming { .. }
+            | GatewayEvent::Unknown => {}
+        }
+    }
+    Err(DialError::EventStreamClosed)
+}
+
+/// Print leg updates until every leg has hung up.
+async fn watch_legs(events: &mut EventStream, legs: &mut Legs, device_id: &str) -> Result<(), DialError> {
+    println!("{} Following conference — Ctrl-C stops watching", "◎".cyan());
+    while let Some(event) = events.next().await? {
+        match event {
+            GatewayEvent::LegStatus { number, state, conference, .. } => {
+                legs.update(&number, state, conference);
+                if legs.all_disconnected() {
+                    return Ok(());
+                }
+            }
+            GatewayEvent::Status { state: CallState::Ended, .. } => return Ok(()),
+            GatewayEvent::Disconnected { .. } => {
+                return Err(DialError::DeviceDisconnected { device_id: device_id.to_string() });
+            }
+            GatewayEvent::Status { .. }
+            | GatewayEvent::SmsStatus { .. }
+            | GatewayEvent::UssdResponse { .. }
+            | GatewayEvent::Incoming { .. }
+            | GatewayEvent::Unknown => {}
+        }
+    }
+    Err(DialError::EventStreamClosed)
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%H:%M:%S").to_string()
+}
@@ -0,0 +1,327 @@
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::contacts::{Contact, ContactNumber, Contacts, Imported};
+use crate::dialstring::DialString;
+use crate::errors::DialError;
+use crate::phone;
+use crate::{contactcsv, vcard};
+
+use super::config_region;
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Subcommand)]
+pub enum ContactsCmd {
+    /// Add a contact, or another number to an existing one
+    ///
+    /// Example:  dial contacts add "Alice Smith" +14155550100 --label mobile
+    Add {
+        /// Contact name; `dial call` accepts any unambiguous part of it
+        name: String,
+
+        /// Phone number, in any form `dial call` accepts
+        number: String,
+
+        /// What the number is (e.g. mobile, work, home)
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Free-text notes, replacing any already stored
+        #[arg(long)]
+        notes: Option<String>,
+    },
+
+    /// List contacts, optionally only those whose name contains QUERY
+    List {
+        query: Option<String>,
+    },
+
+    /// Remove a contact, or only one of their numbers
+    ///
+    /// Example:  dial contacts rm alice --number work
+    Rm {
+        /// Contact name (or an unambiguous part of it)
+        name: String,
+
+        /// Only remove this number, given as the number or its label
+        #[arg(long, value_name = "NUMBER|LABEL")]
+        number: Option<String>,
+    },
+
+    /// Import a vCard (.vcf, 3.0 or 4.0) or a Google / Outlook CSV export
+    ///
+    /// Numbers are normalised as `dial call` reads them (see
+    /// `default_region`); ones already in the book are skipped, and entries
+    /// without a name or a valid number are listed as rejected.
+    ///
+    /// Example:  dial contacts import ~/Downloads/contacts.vcf --dry-run
+    Import {
+        file: PathBuf,
+
+        /// Only report what would be imported and rejected; save nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Write every contact as vCard 3.0, to FILE or to stdout
+    Export {
+        file: Option<PathBuf>,
+    },
+}
+
+impl ContactsCmd {
+    /// Local sanity checks, run before the contacts file is touched.
+    pub fn validate(&self) -> Result<(), DialError> {
+        match self {
+            ContactsCmd::Add { name, number, .. } => {
+                if name.trim().is_empty() {
+                    return Err(DialError::EmptyContactName);
+                }
+                phone::check(number)
+            }
+            ContactsCmd::Rm { name, .. } if name.trim().is_empty() => Err(DialError::EmptyContactName),
+            ContactsCmd::List { .. }
+            | ContactsCmd::Rm { .. }
+            | ContactsCmd::Import { .. }
+            | ContactsCmd::Export { .. } => Ok(()),
+        }
+    }
+}
+
+// ── dial contacts add | list | rm | import | export ───────────────────────────
+
+pub fn run(action: ContactsCmd) -> Result<(), DialError> {
+    let mut book = Contacts::load()?;
+
+    match action {
+        ContactsCmd::Add { name, number, label, notes } => {
+            let number = phone::normalize(&number, config_region()?)?;
+            let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+            let contact = book.entry(&name);
+            match contact.numbers.iter_mut().find(|n| n.number == number) {
+                Some(existing) => existing.label = label.or(existing.label.take()),
+                None => contact.numbers.push(ContactNumber { label, number: number.clone() }),
+            }
+            if notes.is_some() {
+                contact.notes = notes.filter(|n| !n.trim().is_empty());
+            }
+            let name = contact.name.clone();
+            book.save()?;
+            println!("{} Saved {} → {} to contacts", "✓".green().bold(), name.cyan(), number.yellow());
+        }
+
+        ContactsCmd::List { query } => {
+            let contacts: Vec<&Contact> = match &query {
+                Some(query) => {
+                    let needle = query.trim().to_lowercase();
+                    book.contacts.iter().filter(|c| c.name.to_lowercase().contains(&needle)).collect()
+                }
+                None => book.contacts.iter().collect(),
+            };
+            if contacts.is_empty() {
+                println!("{} No contacts{}.", "○".dimmed(), if query.is_some() { " match" } else { " yet" });
+                return Ok(());
+            }
+            println!("{} {} contact(s)\n", "●".green().bold(), contacts.len());
+            for contact in contacts {
+                println!("  {} {}", "─".dimmed(), contact.name.cyan());
+                for n in &contact.numbers {
+                    println!("      {} {:<16} {}", "▸".dimmed(), n.number, n.label.as_deref().unwrap_or("").dimmed());
+                }
+                if let Some(notes) = &contact.notes {
+                    println!("      {}", notes.dimmed());
+                }
+            }
+        }
+
+        ContactsCmd::Rm { name, number: None } => {
+            let contact = book.remove(&name)?;
+            book.save()?;
+            println!("{} Removed {} from contacts", "✓".green().bold(), contact.name.cyan());
+        }
+
+        ContactsCmd::Rm { name, number: Some(which) } => {
+            let region = config_region()?;
+            let contact = book.find_mut(&name)?;
+            let wanted = phone::normalize(&which, region).ok();
+            let Some(index) = contact.numbers.iter().position(|n| {
+                Some(&n.number) == wanted.as_ref()
+                    || n.label.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(which.trim()))
+            }) else {
+                return Err(DialError::UnknownContactNumber { name: contact.name.clone(), number: which });
+            };
+            let removed = contact.numbers.remove(index);
+            let name = contact.name.clone();
+            // A contact without numbers has nothing left to dial
+            if contact.numbers.is_empty() && contact.notes.is_none() {
+                book.remove(&name)?;
+            }
+            book.save()?;
+            println!("{} Removed {} from {}", "✓".green().bold(), removed.number.yellow(), name.cyan());
+        }
+
+        ContactsCmd::Import { file, dry_run } => import(&mut book, &file, dry_run)?,
+
+        ContactsCmd::Export { file: Some(file) } => {
+            fs::write(&file, vcard::write(&book.contacts))?;
+            println!(
+                "{} Exported {} contact(s) to {}",
+                "✓".green().bold(),
+                book.contacts.len(),
+                file.display().to_string().cyan()
+            );
+        }
+        ContactsCmd::Export { file: None } => print!("{}", vcard::write(&book.contacts)),
+    }
+    Ok(())
+}
+
+/// Merge the contacts in `file` into `book`, reporting what was added,
+/// skipped and rejected.  Nothing is written with `dry_run`.
+fn import(book: &mut Contacts, file: &Path, dry_run: bool) -> Result<(), DialError> {
+    let raw = fs::read(file)?;
+    let text = String::from_utf8_lossy(&raw);
+    let text = text.trim_start_matches('\u{feff}');
+    let is_vcard = match file.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("vcf" | "vcard") => true,
+        Some("csv") => false,
+        _ => text.trim_start().get(..11).is_some_and(|head| head.eq_ignore_ascii_case("BEGIN:VCARD")),
+    };
+    let entries: Vec<Imported> = if is_vcard { vcard::parse(text) } else { contactcsv::parse(text)? };
+    if entries.is_empty() {
+        return Err(DialError::InvalidImport(format!("no contacts found in {}", file.display())));
+    }
+
+    let region = config_region()?;
+    let (mut added, mut updated, mut numbers, mut duplicates) = (0, 0, 0, 0);
+    let mut rejected: Vec<(String, String, String)> = Vec::new();
+
+    for entry in entries {
+        let Some(name) = entry.name.filter(|n| !n.trim().is_empty()) else {
+            rejected.push((entry.source, "(no name)".into(), "no name".into()));
+            continue;
+        };
+        if entry.numbers.is_empty() {
+            rejected.push((entry.source, name, "no phone number".into()));
+            continue;
+        }
+
+        let mut fresh: Vec<ContactNumber> = Vec::new();
+        for (label, typed) in entry.numbers {
+            // Extensions stay on as keys to press once connected
+            match DialString::parse(&typed, region).map(|dial| dial.to_string()) {
+                Ok(number) if book.owner_of(&number).is_some() || fresh.iter().any(|n| n.number == number) => {
+                    duplicates += 1;
+                }
+                Ok(number) => fresh.push(ContactNumber { label, number }),
+                Err(e) => {
+                    let reason = match e {
+                        DialError::NoCountryCode(_) => format!("'{typed}' has no country code (set default_region)"),
+                        _ => format!("'{typed}' is not a phone number"),
+                    };
+                    rejected.push((entry.source.clone(), name.clone(), reason));
+                }
+            }
+        }
+        if fresh.is_empty() {
+            continue;
+        }
+
+        if book.contains(&name) {
+            updated += 1;
+        } else {
+            added += 1;
+        }
+        numbers += fresh.len();
+        let contact = book.entry(&name);
+        contact.numbers.extend(fresh);
+        if contact.notes.is_none() {
+            contact.notes = entry.notes;
+        }
+    }
+
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    println!(
+        "{} {verb} {} number(s) from {}: {} new contact(s), {} updated",
+        if dry_run { "◎".cyan().bold() } else { "✓".green().bold() },
+        numbers,
+        file.display().to_string().cyan(),
+        added,
+        updated
+    );
+    if duplicates > 0 {
+        println!("  {} {duplicates} number(s) already in contacts — skipped", "○".dimmed());
+    }
+    if !rejected.is_empty() {
+        println!("  {} {} rejected:", "✗".yellow().bold(), rejected.len());
+        for (source, name, reason) in &rejected {
+            println!("      {:<9} {}  {}", source.dimmed(), name, reason.yellow());
+        }
+    }
+
+    if dry_run {
+        println!("  Nothing saved (dry run).");
+    } else if numbers > 0 {
+        book.save()?;
+    }
+    Ok(())
+}
+
+// ── Name resolution for `dial call` ───────────────────────────────────────────
+
+/// The number to dial for the contact `query` names.  A contact with
+/// several numbers gets a prompt (in a terminal) to choose one.
+pub async fn resolve(query: &str) -> Result<(String, ContactNumber), DialError> {
+    let book = Contacts::load()?;
+    let contact = book.find(query)?;
+    let number = match &contact.numbers[..] {
+        [] => return Err(DialError::ContactWithoutNumber(contact.name.clone())),
+        [only] => only.clone(),
+        numbers if !std::io::stdin().is_terminal() => {
+            return Err(DialError::NumberNotChosen {
+                name: contact.name.clone(),
+                labels: numbers.iter().map(|n| n.label_or_number().to_string()).collect(),
+            })
+        }
+        numbers => pick_number(&contact.name, numbers).await?,
+    };
+    Ok((contact.name.clone(), number))
+}
+
+/// Ask which of `numbers` to call; re-asks until a listed one is chosen.
+async fn pick_number(name: &str, numbers: &[ContactNumber]) -> Result<ContactNumber, DialError> {
+    println!("{} {} has {} numbers:", "?".cyan().bold(), name.cyan(), numbers.len());
+    for (i, n) in numbers.iter().enumerate() {
+        println!("  {}. {:<16} {}", i + 1, n.number, n.label.as_deref().unwrap_or("").dimmed());
+    }
+    loop {
+        print!("{} Call which (1-{}, or a label): ", "?".cyan().bold(), numbers.len());
+        std::io::stdout().flush()?;
+        let line = tokio::task::spawn_blocking(|| {
+            let mut line = String::new();
+            std::io::stdin().read_line(&mut line).map(|_| line)
+        })
+        .await
+        .expect("stdin reader panicked")?;
+
+        // End of input: nothing will ever be chosen
+        if line.is_empty() {
+            return Err(DialError::NumberNotChosen {
+                name: name.to_string(),
+                labels: numbers.iter().map(|n| n.label_or_number().to_string()).collect(),
+            });
+        }
+        let choice = line.trim();
+        let picked = match choice.parse::<usize>() {
+            Ok(i) if (1..=numbers.len()).contains(&i) => numbers.get(i - 1),
+            _ => numbers.iter().find(|n| n.label.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(choice))),
+        };
+        if let Some(number) = picked {
+            return Ok(number.clone());
+        }
+    }
+}
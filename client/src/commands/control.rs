@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::api::ControlAction;
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+
+use super::{command_options, show_receipt, show_unconfirmed};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct ControlArgs {
+    /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb)
+    device_id: String,
+
+    /// What to do with the ongoing call (`speaker` toggles the speakerphone)
+    #[arg(value_enum)]
+    action: ControlAction,
+
+    /// Seconds to wait for the device to confirm (0 = don't wait, in which
+    /// case `dial` can't report the call's state).  Defaults to
+    /// `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+impl ControlArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        if self.device_id.trim().is_empty() {
+            return Err(DialError::EmptyDeviceId);
+        }
+        Ok(())
+    }
+}
+
+// ── dial control <device_id> mute|unmute|speaker|hold|resume ─────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: ControlArgs) -> Result<(), DialError> {
+    let ControlArgs { device_id, action, ack_timeout } = args;
+
+    println!(
+        "{} Sending {} to {}",
+        "→".cyan().bold(),
+        action.to_string().yellow(),
+        device_id.yellow()
+    );
+
+    let opts = command_options(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+    let mut receipt = gateway.control(&device_id, action, &opts).await?;
+
+    match receipt.result.take() {
+        Some(outcome) if !outcome.applied => {
+            return Err(DialError::ControlNotApplied {
+                device_id: receipt.device_id,
+                action: action.to_string(),
+                reason: outcome.reason.unwrap_or_else(|| "unknown reason".to_string()),
+            });
+        }
+        Some(outcome) => {
+            println!("{} {}", "✓".green().bold(), describe(action));
+            if let Some(state) = outcome.state {
+                println!("  State  : {}", state.to_string().cyan());
+            }
+        }
+        None => show_unconfirmed(&receipt, &action.to_string(), "report the call's state"),
+    }
+    show_receipt(&receipt);
+    Ok(())
+}
+
+/// Past-tense summary of an applied action.
+pub fn describe(action: ControlAction) -> &'static str {
+    match action {
+        ControlAction::Mute => "Microphone muted",
+        ControlAction::Unmute => "Microphone unmuted",
+        ControlAction::Speaker => "Speakerphone toggled",
+        ControlAction::Hold => "Call on hold",
+        ControlAction::Resume => "Call resumed",
+    }
+}
@@ -0,0 +1,34 @@
+use colored::Colorize;
+
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+
+// ── dial devices ──────────────────────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway) -> Result<(), DialError> {
+    let resp = gateway.devices().await?;
+
+    if resp.devices.is_empty() {
+        println!("{} No devices currently connected.", "○".dimmed());
+    } else {
+        println!("{} {} device(s) connected\n", "●".green().bold(), resp.count);
+        for dev in &resp.devices {
+            println!(
+                "  {} {}  (connected since {})",
+                "─".dimmed(),
+                dev.device_id.cyan(),
+                dev.connected_at.dimmed()
+            );
+            match dev.sims.as_deref() {
+                Some([]) => println!("      {}", "no SIM".dimmed()),
+                Some(sims) => {
+                    for sim in sims {
+                        println!("      {} {}", "▸".dimmed(), sim);
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+    Ok(())
+}
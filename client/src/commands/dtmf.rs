@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::api::validate_dtmf;
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+
+use super::{command_options, show_receipt, show_unconfirmed};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct DtmfArgs {
+    /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb)
+    device_id: String,
+
+    /// Keys to press: 0-9, *, # and A-D (e.g. 1234#)
+    digits: String,
+
+    /// Seconds to wait for the device to confirm (0 = don't wait, in which
+    /// case `dial` can't tell whether a call was connected).  Defaults to
+    /// `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+impl DtmfArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        if self.device_id.trim().is_empty() {
+            return Err(DialError::EmptyDeviceId);
+        }
+        validate_dtmf(&self.digits)
+    }
+}
+
+// ── dial dtmf <device_id> <digits> ────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: DtmfArgs) -> Result<(), DialError> {
+    let DtmfArgs { device_id, digits, ack_timeout } = args;
+
+    println!(
+        "{} Sending DTMF {} to {}",
+        "→".cyan().bold(),
+        digits.yellow(),
+        device_id.yellow()
+    );
+
+    let opts = command_options(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+    let mut receipt = gateway.dtmf(&device_id, &digits, &opts).await?;
+
+    match receipt.result.take() {
+        Some(outcome) if !outcome.played => {
+            return Err(DialError::DtmfNotPlayed {
+                device_id: receipt.device_id,
+                reason: outcome.reason.unwrap_or_else(|| "unknown reason".to_string()),
+            });
+        }
+        Some(_) => println!("{} Playing {} tone(s) into the call", "♪".green().bold(), digits.len()),
+        None => show_unconfirmed(&receipt, "DTMF", "say whether it played"),
+    }
+    show_receipt(&receipt);
+    Ok(())
+}
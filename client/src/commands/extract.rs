@@ -0,0 +1,94 @@
+use std::io::{IsTerminal, Read};
+
+use colored::Colorize;
+
+use crate::errors::DialError;
+use crate::extract::{extract_phones, FoundNumber};
+use crate::phone::Region;
+
+use super::{config_region, picker};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct ExtractArgs {
+    /// Print one JSON object per line instead of text, for scripts:
+    /// {"number", "extension", "found"}
+    #[arg(long)]
+    json: bool,
+}
+
+// ── dial extract < text ───────────────────────────────────────────────────────
+
+pub fn run(args: ExtractArgs) -> Result<(), DialError> {
+    let found = extract_phones(&read_text()?, config_region()?);
+    if found.is_empty() {
+        return Err(DialError::NoPhoneNumberFound);
+    }
+
+    if args.json {
+        for f in &found {
+            let record = serde_json::json!({
+                "number": f.number,
+                "extension": f.extension,
+                "found": f.found,
+            });
+            println!("{record}");
+        }
+        return Ok(());
+    }
+
+    println!("{} {} number(s) found\n", "●".green().bold(), found.len());
+    for f in &found {
+        let ext = f.extension.as_deref().map_or_else(String::new, |e| format!(" ext. {e}"));
+        println!(
+            "  {} {}  {}",
+            "─".dimmed(),
+            format!("{:<24}", format!("{}{ext}", f.number)).cyan(),
+            format!("← {}", one_line(&f.found)).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// The dial string for the phone number in the text on stdin, for
+/// `dial call --from-stdin`.  Several numbers get a picker on the terminal;
+/// `None` when the user backs out of it.
+pub async fn number_from_stdin(region: Option<&Region>) -> Result<Option<String>, DialError> {
+    let mut found = extract_phones(&read_text()?, region);
+    match found.len() {
+        0 => return Err(DialError::NoPhoneNumberFound),
+        1 => {
+            let f = found.remove(0);
+            println!("{} Found {} in the input", "◎".cyan(), one_line(&f.found));
+            return Ok(Some(f.dial_string()));
+        }
+        _ => {}
+    }
+    // stdin is the text, so the picker reads keys from the terminal itself
+    if !std::io::stderr().is_terminal() {
+        return Err(DialError::SeveralNumbersFound { found: found.iter().map(FoundNumber::dial_string).collect() });
+    }
+
+    let rows: Vec<String> = found
+        .iter()
+        .map(|f| format!("{:<20}  ← {}", f.dial_string(), one_line(&f.found)))
+        .collect();
+    let picked = picker::select("Call", rows, 0).await?;
+    Ok(picked.map(|i| found.swap_remove(i).dial_string()))
+}
+
+/// All of stdin.
+fn read_text() -> Result<String, DialError> {
+    if std::io::stdin().is_terminal() {
+        eprintln!("{} Paste the text, then press Ctrl-D", "◎".cyan());
+    }
+    let mut text = String::new();
+    std::io::stdin().read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// `found` with line breaks and runs of spaces collapsed, for display.
+fn one_line(found: &str) -> String {
+    found.split_whitespace().collect::<Vec<_>>().join(" ")
+}
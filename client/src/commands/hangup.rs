@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+use crate::session;
+
+use super::{command_options, resolve_device, show_receipt, show_unconfirmed};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct HangupArgs {
+    /// Device to hang up on.  May be omitted when only one device is connected.
+    device_id: Option<String>,
+
+    /// Seconds to wait for the device to confirm (0 = don't wait, in which
+    /// case `dial` can't tell whether a call was active).  Defaults to
+    /// `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+// ── dial hangup [device_id] ───────────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: HangupArgs) -> Result<(), DialError> {
+    let device_id = resolve_device(gateway, args.device_id).await?;
+
+    println!("{} Hanging up on {}", "→".cyan().bold(), device_id.yellow());
+
+    let opts = command_options(args.ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+    let mut receipt = gateway.hangup(&device_id, &opts).await?;
+
+    match receipt.result.take() {
+        Some(outcome) if outcome.was_active && !outcome.ended => {
+            return Err(DialError::HangupFailed { device_id: receipt.device_id });
+        }
+        Some(outcome) if outcome.was_active => {
+            println!("{} Call ended", "✓".green().bold());
+        }
+        Some(_) => {
+            println!("{} No call was active on the device", "○".dimmed());
+        }
+        // Devices that predate END_CALL results ACK without one
+        None => show_unconfirmed(&receipt, "Hang-up", "say whether a call was active"),
+    }
+    show_receipt(&receipt);
+
+    // ── Close the HFP session a `dial call` may still be holding ──────────
+    if let Some(pid) = session::release(&receipt.device_id) {
+        println!(
+            "  {} Told `dial call` (pid {pid}) to close its HFP session — A2DP will be restored",
+            "♫".cyan()
+        );
+    }
+    Ok(())
+}
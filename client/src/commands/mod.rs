@@ -0,0 +1,221 @@
+//! Handlers for the subcommands that talk to the gateway (and for
+//! `dial contacts`, which only keeps the book they read names from).
+//!
+//! Each handler receives a `&dyn Gateway` built by `main` from the resolved
+//! config, so none of them know which transport (or wrappers) sit behind it.
+
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::api::{CommandOptions, CommandReceipt};
+use crate::bluetooth::{activate_hfp, HfpCodec, HfpSession};
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+use crate::keypad::Keypad;
+use crate::phone::{self, Region};
+use crate::session::{self, Stop};
+
+pub mod answer;
+pub mod call;
+pub mod conference;
+pub mod contacts;
+pub mod control;
+pub mod devices;
+pub mod dtmf;
+pub mod extract;
+pub mod hangup;
+mod picker;
+pub mod reject;
+pub mod sms;
+pub mod status;
+pub mod ussd;
+pub mod watch;
+
+/// The device a command targets: `device_id` if given, otherwise the only
+/// connected device.  With several connected the user has to choose.
+pub async fn resolve_device(
+    gateway: &dyn Gateway,
+    device_id: Option<String>,
+) -> Result<String, DialError> {
+    if let Some(id) = device_id {
+        if id.trim().is_empty() {
+            return Err(DialError::EmptyDeviceId);
+        }
+        return Ok(id);
+    }
+
+    let mut devices: Vec<String> = gateway
+        .devices()
+        .await?
+        .devices
+        .into_iter()
+        .map(|d| d.device_id)
+        .collect();
+    match devices.len() {
+        0 => Err(DialError::NoDevicesConnected),
+        1 => {
+            let id = devices.remove(0);
+            println!("{} Using the only connected device, {}", "◎".cyan(), id.cyan());
+            Ok(id)
+        }
+        _ => Err(DialError::AmbiguousDevice { devices }),
+    }
+}
+
+/// `default_region` for commands that read numbers without talking to the
+/// gateway; none without a config.
+pub fn config_region() -> Result<Option<&'static Region>, DialError> {
+    match Config::load() {
+        Ok(config) => config.default_region.as_deref().map(phone::region).transpose(),
+        Err(DialError::ConfigNotFound { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Tell the user when `number` is not what they typed, because
+/// [`crate::phone::normalize`] reformatted it ("you typed X → dialing +Y").
+pub fn show_normalized(typed: &str, number: &str, verb: &str) {
+    if typed.trim() != number {
+        println!("{} You typed {} → {verb} {}", "◎".cyan(), typed.trim(), number.yellow());
+    }
+}
+
+/// Check `sim` against the SIMs `device_id` reports and describe the line
+/// for display.  Devices that don't list their SIMs (older app versions, or
+/// a device that isn't connected) get the selector passed through unchecked.
+pub async fn check_sim(gateway: &dyn Gateway, device_id: &str, sim: &str) -> Result<String, DialError> {
+    let devices = gateway.devices().await?;
+    let Some(device) = devices.devices.iter().find(|d| d.device_id == device_id) else {
+        return Ok(sim.to_string());
+    };
+    let Some(sims) = &device.sims else {
+        eprintln!(
+            "{} {device_id} does not report its SIMs — it may ignore --sim and use its default line",
+            "warn:".yellow()
+        );
+        return Ok(sim.to_string());
+    };
+    match sims.iter().find(|s| s.matches(sim)) {
+        Some(found) => Ok(found.to_string()),
+        None => Err(DialError::UnknownSim {
+            device_id: device_id.to_string(),
+            sim: sim.to_string(),
+            available: sims.iter().map(ToString::to_string).collect(),
+        }),
+    }
+}
+
+// ── Sending commands ──────────────────────────────────────────────────────────
+
+/// Options for one command the user asked for, acknowledged within
+/// `ack_timeout`.
+///
+/// Every command carries a fresh idempotency key, which is also what lets
+/// [`Retrying`](crate::retry::Retrying) send it again when the response is
+/// lost: the gateway answers the retry with the first attempt's result
+/// instead of dispatching twice.  Hardly any command is safe to repeat — an
+/// SMS or a USSD purchase would go out twice, keys would take an IVR
+/// somewhere else, `speaker` would toggle back, and a second answer, reject
+/// or hang-up would report that nothing was ringing.
+pub fn command_options(ack_timeout: Duration) -> CommandOptions {
+    CommandOptions {
+        ack_timeout: Some(ack_timeout),
+        idempotency_key: Some(uuid::Uuid::new_v4().to_string()),
+    }
+}
+
+/// Report a command whose ACK carried no outcome: `what` delivered, though
+/// the device did not `unsaid`, or only sent.
+pub fn show_unconfirmed<R>(receipt: &CommandReceipt<R>, what: &str, unsaid: &str) {
+    if receipt.delivered {
+        println!("{} {what} delivered — device did not {unsaid}", "✓".green().bold());
+    } else {
+        println!("{} {what} sent (delivery not confirmed)", "✓".green().bold());
+    }
+}
+
+/// The lines that close every command's report.
+pub fn show_receipt<R>(receipt: &CommandReceipt<R>) {
+    println!("  Device : {}", receipt.device_id.cyan());
+    println!("  Command: {}", receipt.command_id.dimmed());
+}
+
+// ── Call audio on the laptop ──────────────────────────────────────────────────
+
+/// Switch the phone's Bluetooth card to HFP and open the SCO audio channel.
+///
+/// `activate_hfp` switches the profile AND spawns pw-loopback processes that
+/// keep the Bluetooth SCO audio socket alive.  Without this the HFP nodes
+/// stay SUSPENDED → dead silence.  The session lasts until it is dropped.
+/// A failure is only a warning: the call goes on with audio on the phone.
+pub fn open_hfp(card: &str) -> Option<HfpSession> {
+    print!("{} Opening HFP call-audio channel… ", "♫".cyan());
+    match activate_hfp(card) {
+        Ok(session) => {
+            match &session.codec {
+                HfpCodec::PhoneGateway =>
+                    println!("{} (Audio Gateway — phone HFP active)",
+                        "done".green().bold()),
+                codec =>
+                    println!("{} ({})", "done".green().bold(), codec.label()),
+            }
+            Some(session)
+        }
+        Err(e) => {
+            eprintln!();
+            eprintln!("{} BT HFP activation failed: {e}", "warn:".yellow());
+            eprintln!("  Continuing — audio will stay on the phone speaker.");
+            None
+        }
+    }
+}
+
+/// Block until Enter (or `dial hangup`) so the caller's `HfpSession` (and
+/// its loopback processes) stays alive for the full duration of the call.
+/// Once the caller drops the session the loopbacks are killed and A2DP is
+/// restored.
+///
+/// `keypad` is used when it already owns stdin; otherwise stdin is read on a
+/// plain thread so a pending read doesn't hold up process exit.
+pub async fn hold_hfp(keypad: Option<&mut Keypad>) {
+    println!();
+    println!(
+        "  {} Audio is routed to your headset via BT HFP (SCO active).",
+        "♫".cyan()
+    );
+    println!(
+        "  {} Keep this terminal open for the duration of the call.",
+        "!".yellow()
+    );
+    println!(
+        "  Press {} when you hang up to restore A2DP stereo.",
+        "Enter".cyan()
+    );
+    let enter = async {
+        match keypad {
+            Some(keys) => while keys.next().await.is_some_and(|k| k != '\n') {},
+            None => {
+                let (enter_tx, enter_rx) = tokio::sync::oneshot::channel();
+                std::thread::spawn(move || {
+                    let mut buf = String::new();
+                    let _ = std::io::stdin().read_line(&mut buf);
+                    let _ = enter_tx.send(());
+                });
+                let _ = enter_rx.await;
+            }
+        }
+    };
+    tokio::select! {
+        _ = enter => {}
+        stop = session::terminated() => match stop {
+            Stop::HungUp => println!("  {} Call hung up via `dial hangup`", "■".cyan()),
+            Stop::Interrupted => println!("  {} Interrupted — the call continues on the phone", "■".cyan()),
+        },
+    }
+    println!(
+        "  {} Restoring A2DP stereo…",
+        "♫".cyan()
+    );
+}
@@ -0,0 +1,130 @@
+//! The fuzzy finder `dial call` shows when run without a number: recent
+//! calls and every contact's numbers in one list, each with the E.164
+//! number it dials, then the device to call from when several are
+//! connected.
+
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::FuzzySelect;
+
+use crate::contacts::Contacts;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+use crate::history::{self, Entry};
+
+/// Recent calls offered ahead of the contacts.
+const RECENT: usize = 20;
+
+/// Rows visible at once; the rest scroll.
+const ROWS: usize = 12;
+
+/// Someone the picker offers to call.
+struct Choice {
+    number: String,
+    /// `Alice Smith · mobile` when the number is in the contacts
+    who: Option<String>,
+    /// The latest call to the number
+    recent: Option<Entry>,
+}
+
+/// Pick who to call and, unless `device_id` is given, the device to call
+/// from.  `None` when the user backs out with Esc.
+pub async fn pick(
+    gateway: &dyn Gateway,
+    device_id: Option<String>,
+) -> Result<Option<(String, String)>, DialError> {
+    let book = Contacts::load()?;
+    let who = |number: &str| {
+        let contact = book.owner_of(number)?;
+        let entry = contact.numbers.iter().find(|n| n.number == number)?;
+        Some(match &entry.label {
+            Some(label) => format!("{} · {label}", contact.name),
+            None => contact.name.clone(),
+        })
+    };
+
+    let mut choices: Vec<Choice> = history::recent(RECENT)
+        .into_iter()
+        .map(|entry| Choice { number: entry.number.clone(), who: who(&entry.number), recent: Some(entry) })
+        .collect();
+    for contact in &book.contacts {
+        for entry in &contact.numbers {
+            if !choices.iter().any(|c| c.number == entry.number) {
+                choices.push(Choice { number: entry.number.clone(), who: who(&entry.number), recent: None });
+            }
+        }
+    }
+    if choices.is_empty() {
+        return Err(DialError::NothingToPick);
+    }
+
+    let width = choices.iter().map(|c| c.who.as_deref().map_or(0, |w| w.chars().count())).max().unwrap_or(0);
+    let rows: Vec<String> = choices
+        .iter()
+        .map(|c| {
+            let who = c.who.as_deref().unwrap_or("");
+            let when = c.recent.as_ref().map_or_else(String::new, |e| {
+                format!("  called {} from {}", local_date(&e.timestamp), e.device_id)
+            });
+            format!("{who:<width$}  {:<16}{when}", c.number).trim_end().to_string()
+        })
+        .collect();
+
+    let Some(index) = select("Call", rows, 0).await? else { return Ok(None) };
+    let choice = &choices[index];
+
+    let device_id = match device_id {
+        Some(id) => id,
+        None => {
+            let preferred = choice.recent.as_ref().map(|e| e.device_id.as_str());
+            match pick_device(gateway, preferred).await? {
+                Some(id) => id,
+                None => return Ok(None),
+            }
+        }
+    };
+    Ok(Some((device_id, choice.number.clone())))
+}
+
+/// The only connected device, or the one the user picks; the device last
+/// used for this number comes first.
+pub async fn pick_device(gateway: &dyn Gateway, preferred: Option<&str>) -> Result<Option<String>, DialError> {
+    let mut devices = gateway.devices().await?.devices;
+    match devices.len() {
+        0 => return Err(DialError::NoDevicesConnected),
+        1 => return Ok(devices.pop().map(|d| d.device_id)),
+        _ => {}
+    }
+
+    let rows: Vec<String> = devices
+        .iter()
+        .map(|d| {
+            let sims = d.sims.iter().flatten().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+            format!("{}  {}", d.device_id, sims)
+        })
+        .collect();
+    let default = preferred.and_then(|id| devices.iter().position(|d| d.device_id == id)).unwrap_or(0);
+    let picked = select("From", rows, default).await?;
+    Ok(picked.map(|i| devices.swap_remove(i).device_id))
+}
+
+/// Run a fuzzy select over `rows` on the terminal.
+pub async fn select(prompt: &'static str, rows: Vec<String>, default: usize) -> Result<Option<usize>, DialError> {
+    tokio::task::spawn_blocking(move || {
+        FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(&rows)
+            .default(default)
+            .max_length(ROWS)
+            .interact_opt()
+    })
+    .await
+    .expect("picker panicked")
+    .map_err(|e| DialError::Prompt(e.to_string()))
+}
+
+/// `Mar 14 09:30` in local time, or the raw timestamp if it won't parse.
+fn local_date(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.with_timezone(&chrono::Local).format("%b %d %H:%M").to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+
+use super::{command_options, resolve_device, show_receipt, show_unconfirmed};
+use super::sms::follow_sms;
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct RejectArgs {
+    /// Device the call is ringing on.  May be omitted when only one device
+    /// is connected.
+    device_id: Option<String>,
+
+    /// Text the caller after declining (e.g. --sms "busy, call you back").
+    /// Skipped when the caller withheld their number.
+    #[arg(long, value_name = "TEXT")]
+    sms: Option<String>,
+
+    /// Seconds to wait for the reply text to be reported as sent and
+    /// delivered (0 = return once the call is rejected)
+    #[arg(long, value_name = "SECS", default_value = "30")]
+    wait: u64,
+
+    /// Seconds to wait for the device to confirm (0 = don't wait, in which
+    /// case `dial` can't tell whether a call was ringing).  Defaults to
+    /// `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+impl RejectArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        match &self.sms {
+            Some(text) if text.trim().is_empty() => Err(DialError::EmptyMessage),
+            _ => Ok(()),
+        }
+    }
+}
+
+// ── dial reject [device_id] [--sms TEXT] ──────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: RejectArgs) -> Result<(), DialError> {
+    let RejectArgs { device_id, sms, wait, ack_timeout } = args;
+    let device_id = resolve_device(gateway, device_id).await?;
+
+    println!("{} Rejecting the call on {}", "→".cyan().bold(), device_id.yellow());
+
+    // Subscribe first so the reply's SMS_STATUS can't slip past
+    let mut events = if sms.is_some() && wait > 0 {
+        Some(gateway.events(Some(&device_id)).await?)
+    } else {
+        None
+    };
+
+    let opts = command_options(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+    let mut receipt = gateway.reject(&device_id, sms.as_deref(), &opts).await?;
+
+    let caller = match receipt.result.take() {
+        Some(outcome) if !outcome.ringing => {
+            return Err(DialError::NothingRinging { device_id: receipt.device_id });
+        }
+        Some(outcome) if !outcome.rejected => {
+            return Err(DialError::RejectFailed {
+                device_id: receipt.device_id,
+                reason: outcome.reason.unwrap_or_else(|| "unknown reason".to_string()),
+            });
+        }
+        Some(outcome) => {
+            match &outcome.number {
+                Some(number) => println!("{} Rejected the call from {}", "✓".green().bold(), number.yellow()),
+                None => println!("{} Rejected the call", "✓".green().bold()),
+            }
+            // Known to be withheld only when the device says so
+            Some(outcome.number)
+        }
+        None => {
+            show_unconfirmed(&receipt, "Reject", "say whether a call was ringing");
+            None
+        }
+    };
+    show_receipt(&receipt);
+
+    // ── Reply text ───────────────────────────────────────────────────────
+    let Some(text) = sms else {
+        return Ok(());
+    };
+    let number = match caller {
+        Some(Some(number)) => number,
+        Some(None) => {
+            eprintln!("{} The caller withheld their number — no text sent", "warn:".yellow());
+            return Ok(());
+        }
+        // An older app ignores `sms`; the events show whether it went out
+        None => "the caller".to_string(),
+    };
+    println!("  Reply  : {}", format!("\"{text}\"").cyan());
+
+    if let Some(ref mut stream) = events {
+        println!();
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(wait),
+            follow_sms(stream, &receipt.device_id, &receipt.command_id, &number),
+        )
+        .await;
+        match outcome {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(e),
+            Err(_) => println!(
+                "{} No delivery report within {wait}s — not every carrier sends one",
+                "○".dimmed()
+            ),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use std::io::{IsTerminal, Read};
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::events::{EventStream, GatewayEvent, SmsState};
+use crate::gateway::Gateway;
+use crate::phone;
+use crate::sms::{self, Encoding};
+
+use super::{command_options, show_normalized, show_receipt};
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct SmsArgs {
+    /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb)
+    device_id: String,
+
+    /// Phone number in E.164 format (e.g. +919876543210), or as written
+    /// locally when `default_region` is set
+    number: String,
+
+    /// Message text.  Read from stdin when omitted or `-`, e.g.
+    /// `echo "Running late" | dial sms <device> <number>`
+    message: Option<String>,
+
+    /// Seconds to wait for the device to report the message as sent and
+    /// delivered (0 = return once the device has accepted it)
+    #[arg(long, value_name = "SECS", default_value = "30")]
+    wait: u64,
+
+    /// Seconds to wait for the device to acknowledge the command
+    /// (0 = don't wait).  Defaults to `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+impl SmsArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        if self.device_id.trim().is_empty() {
+            return Err(DialError::EmptyDeviceId);
+        }
+        phone::check(&self.number)
+    }
+}
+
+// ── dial sms <device_id> <number> [message] ───────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: SmsArgs) -> Result<(), DialError> {
+    let SmsArgs { device_id, number: typed, message, wait, ack_timeout } = args;
+    let number = config.normalize(&typed)?;
+
+    let text = match message.filter(|m| m != "-") {
+        Some(m) => m,
+        None => read_stdin()?,
+    };
+    if text.trim().is_empty() {
+        return Err(DialError::EmptyMessage);
+    }
+
+    // ── Length / segment summary ─────────────────────────────────────────
+    let layout = sms::analyze(&text);
+    show_normalized(&typed, &number, "texting");
+    println!(
+        "{} Sending SMS from {} → {}",
+        "→".cyan().bold(),
+        device_id.yellow(),
+        number.yellow()
+    );
+    println!(
+        "  Length : {} chars · {} segment{} ({})",
+        layout.chars,
+        layout.segments.to_string().cyan(),
+        if layout.segments == 1 { "" } else { "s" },
+        layout.encoding,
+    );
+    if layout.encoding == Encoding::Ucs2 {
+        let shown: String = layout.non_gsm.iter().take(10).map(|c| format!(" {c:?}")).collect();
+        eprintln!(
+            "{} Not in the GSM-7 alphabet:{shown} — sending as UCS-2 (70 chars per segment instead of 160)",
+            "warn:".yellow(),
+        );
+    }
+
+    // Subscribe first so an SMS_STATUS can't slip past before we listen
+    let mut events = if wait > 0 {
+        Some(gateway.events(Some(&device_id)).await?)
+    } else {
+        None
+    };
+
+    let opts = command_options(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+    let receipt = gateway.sms(&device_id, &number, &text, &opts).await?;
+
+    if receipt.delivered {
+        println!("{} SMS command delivered — device acknowledged", "✓".green().bold());
+    } else {
+        println!("{} SMS command sent (delivery not confirmed)", "✓".green().bold());
+    }
+    show_receipt(&receipt);
+
+    if let Some(ref mut stream) = events {
+        println!();
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(wait),
+            follow_sms(stream, &receipt.device_id, &receipt.command_id, &number),
+        )
+        .await;
+        match outcome {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(e),
+            Err(_) => println!(
+                "{} No delivery report within {wait}s — not every carrier sends one",
+                "○".dimmed()
+            ),
+        }
+    }
+    Ok(())
+}
+
+// ── SMS status ────────────────────────────────────────────────────────────────
+
+/// Print SMS_STATUS updates for `command_id` until the message is delivered
+/// or fails.  Also follows the reply text `dial reject --sms` sends.
+pub async fn follow_sms(
+    events: &mut EventStream,
+    device_id: &str,
+    command_id: &str,
+    number: &str,
+) -> Result<(), DialError> {
+    while let Some(event) = events.next().await? {
+        match event {
+            GatewayEvent::SmsStatus { state, command_id: event_cmd, reason } => {
+                if event_cmd.as_deref() != Some(command_id) {
+                    continue;
+                }
+                let (icon, label) = match state {
+                    SmsState::Sent      => ("✓".green(), state.label().green()),
+                    SmsState::Delivered => ("✓".green().bold(), state.label().green().bold()),
+                    SmsState::Failed    => ("✗".red().bold(), state.label().red()),
+                };
+                println!(
+                    "  {}  {} {:<13} {}",
+                    chrono::Local::now().format("%H:%M:%S").to_string().dimmed(),
+                    icon,
+                    label,
+                    reason.as_deref().unwrap_or_default().dimmed(),
+                );
+
+                match state {
+                    SmsState::Sent => {}
+                    SmsState::Delivered => return Ok(()),
+                    SmsState::Failed => {
+                        return Err(DialError::SmsFailed {
+                            number: number.to_string(),
+                            reason: reason.unwrap_or_else(|| "unknown error".to_string()),
+                        });
+                    }
+                }
+            }
+            GatewayEvent::Disconnected { .. } => {
+                return Err(DialError::DeviceDisconnected { device_id: device_id.to_string() });
+            }
+            GatewayEvent::Status { .. }
+            | GatewayEvent::UssdResponse { .. }
+            | GatewayEvent::LegStatus { .. }
+            | GatewayEvent::Incoming { .. }
+            | GatewayEvent::Unknown => {}
+        }
+    }
+
+    Err(DialError::EventStreamClosed)
+}
+
+/// The whole of stdin, minus the trailing newline `echo` adds.
+fn read_stdin() -> Result<String, DialError> {
+    if std::io::stdin().is_terminal() {
+        eprintln!("{} Type the message, then press Ctrl-D", "◎".cyan());
+    }
+    let mut text = String::new();
+    std::io::stdin().read_to_string(&mut text)?;
+    Ok(text.trim_end_matches(['\n', '\r']).to_string())
+}
@@ -0,0 +1,50 @@
+use colored::Colorize;
+
+use crate::api::PROTOCOL_VERSION;
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::gateway::Gateway;
+
+// ── dial status ───────────────────────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config) -> Result<(), DialError> {
+    let health = gateway.health().await?;
+
+    println!("{} Gateway is reachable", "✓".green().bold());
+    println!("  URL:               {}", gateway.endpoint().cyan());
+    println!("  Transport:         {}", gateway.transport());
+    println!("  TLS:               {}", describe_tls(config));
+    println!("  Status:            {}", health.status);
+    println!(
+        "  Version:           {}",
+        health.version.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "  Protocol:          v{} (dial v{PROTOCOL_VERSION})",
+        health.protocol_version.map_or("?".to_string(), |v| v.to_string())
+    );
+    match health.check_protocol() {
+        Ok(None) => {}
+        Ok(Some(warning)) => println!("                     {}", warning.yellow()),
+        Err(e) => println!("                     {}", e.to_string().replace('\n', " ").red()),
+    }
+    println!("  Uptime:            {:.0}s", health.uptime);
+    println!("  Connected devices: {}", health.connected_devices);
+    Ok(())
+}
+
+/// One-line summary of how the connection is protected.
+fn describe_tls(config: &Config) -> String {
+    if config.unix_socket().is_some() {
+        return "off (local socket — nothing crosses the network)".to_string();
+    }
+    if !config.uses_tls() {
+        return format!("{}", "off — token is sent in cleartext".yellow());
+    }
+    let trust = match (&config.cert_sha256, &config.ca_cert) {
+        (Some(_), _) => "pinned certificate".to_string(),
+        (None, Some(ca)) => format!("CA from {ca}"),
+        (None, None) => "system roots".to_string(),
+    };
+    format!("{} ({trust})", "on".green())
+}
@@ -0,0 +1,149 @@
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::api::validate_ussd;
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::events::{EventStream, GatewayEvent};
+use crate::gateway::Gateway;
+
+use super::command_options;
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct UssdArgs {
+    /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb)
+    device_id: String,
+
+    /// USSD code, e.g. '*123#' (quote it — most shells treat `#` as a comment)
+    code: String,
+
+    /// Seconds to wait for the carrier's reply
+    #[arg(long, value_name = "SECS", default_value = "30")]
+    timeout: u64,
+
+    /// Seconds to wait for the device to acknowledge the command
+    /// (0 = don't wait).  Defaults to `ack_timeout_secs` in config, or 5.
+    #[arg(long, value_name = "SECS")]
+    ack_timeout: Option<u64>,
+}
+
+impl UssdArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        if self.device_id.trim().is_empty() {
+            return Err(DialError::EmptyDeviceId);
+        }
+        validate_ussd(&self.code)
+    }
+}
+
+// ── dial ussd <device_id> <code> ──────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: UssdArgs) -> Result<(), DialError> {
+    let UssdArgs { device_id, mut code, timeout, ack_timeout } = args;
+    let interactive = std::io::stdin().is_terminal();
+
+    // One subscription for the whole session so no reply slips past
+    let mut events = gateway.events(Some(&device_id)).await?;
+
+    loop {
+        println!("{} Sending USSD {} via {}", "→".cyan().bold(), code.yellow(), device_id.yellow());
+
+        let opts = command_options(ack_timeout.map_or_else(|| config.ack_timeout(), Duration::from_secs));
+        let receipt = gateway.ussd(&device_id, &code, &opts).await?;
+
+        let reply = tokio::time::timeout(
+            Duration::from_secs(timeout),
+            await_reply(&mut events, &receipt.device_id, &receipt.command_id),
+        )
+        .await
+        .map_err(|_| DialError::UssdTimeout { code: code.clone(), timeout_secs: timeout })??;
+
+        let message = match reply {
+            Ok(message) => message,
+            Err(reason) => return Err(DialError::UssdFailed { code, reason }),
+        };
+        println!("{} Carrier replied:", "✓".green().bold());
+        for line in message.lines() {
+            println!("  {} {}", "│".dimmed(), line);
+        }
+
+        // ── Multi-step menus: answer with the chained code ────────────────
+        // The device can't reply inside the carrier's session (see
+        // `UssdRunner`), so each step starts a new one
+        if !interactive || !looks_like_menu(&message) {
+            return Ok(());
+        }
+        let Some(choice) = prompt_choice().await? else {
+            return Ok(());
+        };
+        code = chain(&code, &choice);
+        validate_ussd(&code)?;
+        println!();
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+/// Wait for the USSD_RESPONSE to `command_id`: `Ok(text)` or `Err(reason)`.
+async fn await_reply(
+    events: &mut EventStream,
+    device_id: &str,
+    command_id: &str,
+) -> Result<Result<String, String>, DialError> {
+    while let Some(event) = events.next().await? {
+        match event {
+            GatewayEvent::UssdResponse { command_id: event_cmd, ok, message } => {
+                if event_cmd.as_deref() != Some(command_id) {
+                    continue;
+                }
+                return Ok(if ok { Ok(message) } else { Err(message) });
+            }
+            GatewayEvent::Disconnected { .. } => {
+                return Err(DialError::DeviceDisconnected { device_id: device_id.to_string() });
+            }
+            GatewayEvent::Status { .. }
+            | GatewayEvent::SmsStatus { .. }
+            | GatewayEvent::LegStatus { .. }
+            | GatewayEvent::Incoming { .. }
+            | GatewayEvent::Unknown => {}
+        }
+    }
+
+    Err(DialError::EventStreamClosed)
+}
+
+/// A reply offering numbered options ("1. Balance", "2) Data") expects an
+/// answer; anything else ends the session.
+fn looks_like_menu(message: &str) -> bool {
+    message.lines().any(|line| {
+        let line = line.trim_start();
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        digits > 0 && matches!(line[digits..].chars().next(), Some('.' | ')' | ':' | '-' | ' '))
+    })
+}
+
+/// Read the user's menu choice; `None` on an empty line or end of input.
+async fn prompt_choice() -> Result<Option<String>, DialError> {
+    print!("{} Reply (Enter to finish): ", "?".cyan().bold());
+    std::io::stdout().flush()?;
+    let line = tokio::task::spawn_blocking(|| {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line).map(|_| line)
+    })
+    .await
+    .expect("stdin reader panicked")?;
+
+    let choice = line.trim().trim_end_matches('#').to_string();
+    Ok(if choice.is_empty() { None } else { Some(choice) })
+}
+
+/// `*123#` + `2` → `*123*2#`, the form most carriers accept for menu
+/// navigation outside a session.
+fn chain(code: &str, choice: &str) -> String {
+    format!("{}*{}#", code.trim_end_matches('#'), choice)
+}
@@ -0,0 +1,154 @@
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::events::{EventStream, GatewayEvent, IncomingState};
+use crate::gateway::Gateway;
+use crate::retry::RetryPolicy;
+use crate::session;
+
+// ── Arguments ─────────────────────────────────────────────────────────────────
+
+#[derive(clap::Args)]
+pub struct WatchArgs {
+    /// Print calls ringing on the phone, and whether they were answered or
+    /// missed (the only activity `dial watch` follows so far)
+    #[arg(long, required = true)]
+    incoming: bool,
+
+    /// Only watch this device (default: every connected device)
+    #[arg(long = "device", value_name = "DEVICE_ID")]
+    device_id: Option<String>,
+
+    /// Print one JSON object per line instead of text, for scripts:
+    /// {"timestamp", "deviceId", "state", "number", "name"}
+    #[arg(long)]
+    json: bool,
+}
+
+impl WatchArgs {
+    /// Local sanity checks, run before any network traffic.
+    pub fn validate(&self) -> Result<(), DialError> {
+        match &self.device_id {
+            Some(id) if id.trim().is_empty() => Err(DialError::EmptyDeviceId),
+            _ => Ok(()),
+        }
+    }
+}
+
+// ── dial watch --incoming ─────────────────────────────────────────────────────
+
+pub async fn run(gateway: &dyn Gateway, config: &Config, args: WatchArgs) -> Result<(), DialError> {
+    let WatchArgs { incoming: _, device_id, json } = args;
+
+    // Text goes to stderr in JSON mode so stdout stays one object per line
+    let notice = |line: String| if json { eprintln!("{line}") } else { println!("{line}") };
+    notice(format!(
+        "{} Watching for incoming calls on {} — Ctrl-C stops",
+        "◎".cyan(),
+        device_id.as_deref().map_or_else(|| "every device".to_string(), |id| id.yellow().to_string()),
+    ));
+
+    let policy = config.retry_policy();
+    tokio::select! {
+        outcome = watch(gateway, &policy, device_id.as_deref(), json) => outcome,
+        _ = session::terminated() => Ok(()),
+    }
+}
+
+/// Keep an event stream open, reopening it whenever it drops.  Gives up
+/// after `policy.max_attempts` consecutive failures to reach the gateway.
+async fn watch(
+    gateway: &dyn Gateway,
+    policy: &RetryPolicy,
+    device_id: Option<&str>,
+    json: bool,
+) -> Result<(), DialError> {
+    let mut failures = 0;
+    loop {
+        let error = match gateway.events(device_id).await {
+            Ok(mut events) => {
+                failures = 0;
+                match print_incoming(&mut events, json).await {
+                    Ok(()) => return Ok(()),
+                    Err(e) => e,
+                }
+            }
+            Err(e) => e,
+        };
+
+        let recoverable =
+            matches!(error, DialError::EventStreamClosed | DialError::Http(_)) || error.is_transient();
+        failures += 1;
+        if !recoverable || failures >= policy.max_attempts.max(1) {
+            return Err(error);
+        }
+        let delay = policy.backoff(failures - 1);
+        eprintln!(
+            "{} Event stream interrupted ({error}) — reconnecting in {:.1}s",
+            "warn:".yellow(),
+            delay.as_secs_f64()
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Print every incoming-call event until the stream ends.
+async fn print_incoming(events: &mut EventStream, json: bool) -> Result<(), DialError> {
+    while let Some(event) = events.next().await? {
+        match event {
+            GatewayEvent::Incoming { device_id, state, number, name, timestamp } => {
+                if json {
+                    let timestamp = timestamp.unwrap_or_else(|| {
+                        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+                    });
+                    let record = serde_json::json!({
+                        "timestamp": timestamp,
+                        "deviceId": device_id,
+                        "state": state,
+                        "number": number,
+                        "name": name,
+                    });
+                    println!("{record}");
+                    continue;
+                }
+
+                let (icon, label) = match state {
+                    IncomingState::Ringing  => ("☎".cyan().bold(), state.label().cyan()),
+                    IncomingState::Answered => ("✓".green().bold(), state.label().green()),
+                    IncomingState::Missed   => ("✗".yellow().bold(), state.label().yellow()),
+                };
+                let caller = match (number, name) {
+                    (Some(number), Some(name)) => format!("{} ({number})", name.bold()),
+                    (Some(number), None) => number,
+                    (None, Some(name)) => name,
+                    (None, None) => "withheld number".dimmed().to_string(),
+                };
+                println!(
+                    "  {}  {} {:<13} {} · {}",
+                    local_time(timestamp.as_deref()).dimmed(),
+                    icon,
+                    label,
+                    caller,
+                    device_id.cyan()
+                );
+            }
+            GatewayEvent::Status { .. }
+            | GatewayEvent::SmsStatus { .. }
+            | GatewayEvent::UssdResponse { .. }
+            | GatewayEvent::LegStatus { .. }
+            | GatewayEvent::Disconnected { .. }
+            | GatewayEvent::Unknown => {}
+        }
+    }
+    Err(DialError::EventStreamClosed)
+}
+
+/// `HH:MM:SS` in local time for a gateway timestamp, or for now.
+fn local_time(timestamp: Option<&str>) -> String {
+    timestamp
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map_or_else(chrono::Local::now, |t| t.with_timezone(&chrono::Local))
+        .format("%H:%M:%S")
+        .to_string()
+}
@@ -1,30 +1,119 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
 use crate::errors::DialError;
+use crate::phone::{self, Region};
+use crate::retry::RetryPolicy;
 
 /// Contents of `~/.config/phoneconnect/config.toml`
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
-    /// Gateway HTTP base URL, e.g. "http://10.61.214.187:3000"
+    /// Gateway base URL, e.g. "http://10.61.214.187:3000",
+    /// "https://gateway.lan:3443", or "unix:///run/phoneconnect.sock" to talk
+    /// to a gateway on this machine over its Unix socket
     pub server_url: String,
 
     /// Bearer token that matches GATEWAY_TOKENS on the server
     pub token: String,
 
+    /// Proxy every gateway request goes through:
+    /// "socks5://127.0.0.1:1080" (e.g. `ssh -D 1080 jumphost`), "socks5h://…"
+    /// to resolve the gateway's name on the far side, or "http://proxy:3128".
+    /// Unset means the usual `HTTPS_PROXY` / `ALL_PROXY` environment variables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// PEM file with extra trust anchors for an `https://` gateway — its
+    /// self-signed certificate or the CA that issued it.  `~/` is expanded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<String>,
+
+    /// SHA-256 fingerprint the gateway's certificate must match
+    /// (`openssl x509 -noout -fingerprint -sha256 -in cert.pem`).
+    /// Pinning alone is enough to trust a self-signed certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_sha256: Option<String>,
+
     /// Bluetooth MAC of the phone to use for HFP audio routing.
     /// When set, `dial call` will auto-switch BT to HFP without --bt-mac.
     /// Format: AA:BB:CC:DD:EE:FF
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bt_mac: Option<String>,
+
+    /// Seconds `dial call` waits for the device to ACK a command.
+    /// 0 disables the wait (report "sent" as soon as the gateway forwards it).
+    /// Defaults to [`DEFAULT_ACK_TIMEOUT_SECS`] when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ack_timeout_secs: Option<u64>,
+
+    /// SIM `dial call` uses when neither `--sim` nor [`Config::sim_by_number`]
+    /// picks one: a slot number ("1", "2") or a SIM label.  Unset leaves the
+    /// choice to the phone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_sim: Option<String>,
+
+    /// Fixed line per number (`[sim_by_number]` table), e.g.
+    /// `"+14155550100" = "Work"`, so a contact is always called from the
+    /// same SIM.  Takes precedence over `default_sim`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sim_by_number: Option<BTreeMap<String, String>>,
+
+    /// Numbers `dial call` withholds caller ID for without `--private`.
+    /// `*` matches any run of digits, e.g. `["+1800*", "+44 20 7946 0000"]`;
+    /// like numbers dialled, those without `+` are read in `default_region`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_numbers: Option<Vec<String>>,
+
+    /// Carrier code a device dials to withhold caller ID (`[caller_id_prefix]`
+    /// table keyed by device ID), e.g. `android_abc123 = "*67"` for North
+    /// American carriers.  Devices without an entry use `#31#`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller_id_prefix: Option<BTreeMap<String, String>>,
+
+    /// Country numbers without `+` are read in, as an ISO code ("US", "GB",
+    /// "IN"): its trunk prefix is dropped and its calling code added, so
+    /// `(415) 555-0100` dials +14155550100.  Unset, such numbers are rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_region: Option<String>,
+
+    /// Backoff for transient errors when dispatching commands (`[retry]` table).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryConfig>,
+}
+
+/// `[retry]` section of the config file.  Unset keys use the built-in
+/// [`RetryPolicy`] defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RetryConfig {
+    /// Total tries including the first; 1 disables retrying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+
+    /// Backoff ceiling for the first retry, doubled on every further one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_delay_ms: Option<u64>,
+
+    /// Upper bound for any single backoff
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delay_ms: Option<u64>,
+
+    /// Sleep through HTTP 429 responses instead of failing
+    /// (same as passing `--wait-on-rate-limit` on every invocation)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_on_rate_limit: Option<bool>,
 }
 
 /// The factory-default URL written by `config init`.
 /// If the config still has this value, auto-discovery is triggered.
 pub const PLACEHOLDER_URL: &str = "http://10.61.214.187:3000";
 
+/// Default for [`Config::ack_timeout_secs`].
+pub const DEFAULT_ACK_TIMEOUT_SECS: u64 = 5;
+
 impl Config {
     // ── Paths ─────────────────────────────────────────────────────────────────
 
@@ -68,7 +157,17 @@ impl Config {
         let default = Config {
             server_url: PLACEHOLDER_URL.to_string(),
             token: "change-me-secret".to_string(),
+            proxy: None,
+            ca_cert: None,
+            cert_sha256: None,
             bt_mac: None,
+            ack_timeout_secs: None,
+            default_sim: None,
+            sim_by_number: None,
+            private_numbers: None,
+            caller_id_prefix: None,
+            default_region: None,
+            retry: None,
         };
 
         let toml_str = toml::to_string_pretty(&default)
@@ -97,11 +196,186 @@ impl Config {
         self.server_url.trim().is_empty() || self.server_url == PLACEHOLDER_URL
     }
 
+    /// Whether requests to the gateway are encrypted.
+    pub fn uses_tls(&self) -> bool {
+        self.server_url.trim_start().to_ascii_lowercase().starts_with("https://")
+    }
+
+    /// Socket path when `server_url` is `unix:///path/to/socket`.
+    pub fn unix_socket(&self) -> Option<PathBuf> {
+        let path = self.server_url.trim().strip_prefix("unix://")?;
+        Some(PathBuf::from(path))
+    }
+
+    /// [`Config::ca_cert`] with a leading `~/` expanded.
+    pub fn ca_cert_path(&self) -> Option<PathBuf> {
+        let raw = self.ca_cert.as_deref()?.trim();
+        match (raw.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => Some(home.join(rest)),
+            _ => Some(PathBuf::from(raw)),
+        }
+    }
+
+    /// How long to wait for a device ACK; zero means don't wait.
+    pub fn ack_timeout(&self) -> Duration {
+        Duration::from_secs(self.ack_timeout_secs.unwrap_or(DEFAULT_ACK_TIMEOUT_SECS))
+    }
+
+    /// SIM to call `number` from: its `[sim_by_number]` entry, else
+    /// `default_sim`.  Keys may be written in any form `dial call` accepts.
+    pub fn sim_for(&self, number: &str) -> Option<&str> {
+        self.sim_by_number
+            .iter()
+            .flatten()
+            .find(|(key, _)| self.normalize(key).unwrap_or_else(|_| canonical_number(key)) == number)
+            .map(|(_, sim)| sim.as_str())
+            .or(self.default_sim.as_deref())
+    }
+
+    /// Whether `number` matches one of [`Config::private_numbers`].
+    pub fn is_private_number(&self, number: &str) -> bool {
+        self.private_numbers
+            .iter()
+            .flatten()
+            .any(|pattern| matches_pattern(&self.canonical_pattern(pattern), number))
+    }
+
+    /// A [`Config::private_numbers`] pattern in E.164 like the numbers it is
+    /// matched against: the part before any `*` is read in
+    /// [`Config::default_region`], or kept as written if that fails.
+    fn canonical_pattern(&self, pattern: &str) -> String {
+        let (head, tail) = pattern.split_at(pattern.find('*').unwrap_or(pattern.len()));
+        match phone::normalize_prefix(head, self.region()) {
+            Ok(head) => format!("{head}{}", canonical_number(tail)),
+            Err(_) => canonical_number(pattern),
+        }
+    }
+
+    /// Caller-ID prefix configured for `device_id`, if any.
+    pub fn caller_id_prefix_for(&self, device_id: &str) -> Option<&str> {
+        self.caller_id_prefix.as_ref()?.get(device_id).map(String::as_str)
+    }
+
+    /// [`Config::default_region`], looked up.  `None` when unset or unknown
+    /// (which [`Config::validate`] rejects).
+    pub fn region(&self) -> Option<&'static Region> {
+        phone::region(self.default_region.as_deref()?).ok()
+    }
+
+    /// `number` in E.164, read in [`Config::default_region`].
+    pub fn normalize(&self, number: &str) -> Result<String, DialError> {
+        phone::normalize(number, self.region())
+    }
+
+    /// Effective retry policy: `[retry]` overrides on top of the defaults.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        let defaults = RetryPolicy::default();
+        let Some(r) = &self.retry else { return defaults };
+        RetryPolicy {
+            max_attempts: r.max_attempts.unwrap_or(defaults.max_attempts),
+            base_delay: r.base_delay_ms.map_or(defaults.base_delay, Duration::from_millis),
+            max_delay: r.max_delay_ms.map_or(defaults.max_delay, Duration::from_millis),
+            wait_on_rate_limit: r.wait_on_rate_limit.unwrap_or(defaults.wait_on_rate_limit),
+        }
+    }
+
     /// Validate that required fields are non-empty.
     pub fn validate(&self) -> Result<(), DialError> {
         if self.token.trim().is_empty() {
             return Err(DialError::Unauthorized);
         }
+        if let Some(path) = self.unix_socket() {
+            if !path.is_absolute() {
+                return Err(DialError::Transport(format!(
+                    "server_url {} must name an absolute socket path (unix:///run/…)",
+                    self.server_url
+                )));
+            }
+            if self.proxy.is_some() {
+                return Err(DialError::Transport(
+                    "proxy cannot be combined with a unix:// server_url".into(),
+                ));
+            }
+        }
+        for sim in self.default_sim.iter().chain(self.sim_by_number.iter().flat_map(|m| m.values())) {
+            crate::api::validate_sim(sim)?;
+        }
+        if let Some(region) = &self.default_region {
+            phone::region(region)?;
+        }
+        for prefix in self.caller_id_prefix.iter().flat_map(|m| m.values()) {
+            crate::api::validate_caller_id_prefix(prefix)?;
+        }
+        if let Some(proxy) = &self.proxy {
+            let scheme = proxy.split("://").next().unwrap_or_default().to_ascii_lowercase();
+            if !matches!(scheme.as_str(), "socks5" | "socks5h" | "http" | "https") || !proxy.contains("://") {
+                return Err(DialError::Transport(format!(
+                    "unsupported proxy '{proxy}' — use socks5://, socks5h://, http:// or https://"
+                )));
+            }
+        }
+        if !self.uses_tls() && (self.ca_cert.is_some() || self.cert_sha256.is_some()) {
+            return Err(DialError::TlsConfig(format!(
+                "ca_cert / cert_sha256 are set but server_url ({}) is not https://",
+                self.server_url
+            )));
+        }
         Ok(())
     }
 }
+
+/// `number` as it is sent to the gateway: digits and `+` (and `*` wildcards)
+/// only, so config entries may be written with spaces or dashes.
+fn canonical_number(number: &str) -> String {
+    number.chars().filter(|c| c.is_ascii_digit() || matches!(c, '+' | '*')).collect()
+}
+
+/// Glob match where `*` stands for any (possibly empty) run of characters.
+fn matches_pattern(pattern: &str, number: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = number.strip_prefix(first) else { return false };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else { return rest.is_empty() };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(extra: &str) -> Config {
+        toml::from_str(&format!("server_url = \"http://localhost:3000\"\ntoken = \"t\"\n{extra}")).unwrap()
+    }
+
+    #[test]
+    fn private_numbers_are_read_in_the_region() {
+        let config = config("default_region = \"US\"\nprivate_numbers = [\"(415) 555-0100\", \"1 800*\", \"+44 20 7946 *\"]");
+        assert!(config.is_private_number("+14155550100"));
+        assert!(config.is_private_number("+18005550199"));
+        assert!(config.is_private_number("+442079460018"));
+        assert!(!config.is_private_number("+14155550101"));
+        assert!(!config.is_private_number("+18885550199"));
+    }
+
+    #[test]
+    fn private_numbers_without_a_region_are_matched_as_written() {
+        let config = config("private_numbers = [\"+1 (415) 555-0100\", \"*0100\", \"0800*\"]");
+        assert!(config.is_private_number("+14155550100"));
+        assert!(config.is_private_number("+442079460100"));
+        assert!(!config.is_private_number("+448001234567"));
+    }
+
+    #[test]
+    fn sim_by_number_is_read_in_the_region_too() {
+        let config = config("default_region = \"GB\"\n[sim_by_number]\n\"020 7946 0018\" = \"Work\"");
+        assert_eq!(config.sim_for("+442079460018"), Some("Work"));
+        assert_eq!(config.sim_for("+442079460019"), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Contacts exported as CSV by Google Contacts and Outlook.
+//!
+//! Columns are found by their header, so both Google layouts work — the
+//! current one (`First Name`, `Phone 1 - Label`, `Phone 1 - Value`) and the
+//! older one (`Given Name`, `Phone 1 - Type`) — as does Outlook's, which has
+//! one column per kind of number (`Mobile Phone`, `Business Phone 2`, …).
+//! Google packs several numbers into one cell with ` ::: `.
+
+use crate::contacts::{normalize_label, Imported};
+use crate::errors::DialError;
+
+/// Where a phone column's label comes from.
+enum Label {
+    /// Google: the `Phone N - Label` column next to it
+    Column(Option<usize>),
+    /// Outlook: the header itself
+    Fixed(Option<String>),
+}
+
+/// Every row of `text` after the header.
+pub fn parse(text: &str) -> Result<Vec<Imported>, DialError> {
+    let invalid = DialError::InvalidImport;
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(text.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| invalid(e.to_string()))?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    let column = |names: &[&str]| headers.iter().position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)));
+
+    let full_name = column(&["Name", "Display Name"]);
+    let first = column(&["First Name", "Given Name"]);
+    let middle = column(&["Middle Name", "Additional Name"]);
+    let last = column(&["Last Name", "Family Name"]);
+    let org = column(&["Organization Name", "Organization 1 - Name", "Company"]);
+    let notes = column(&["Notes"]);
+
+    let mut phones: Vec<(usize, Label)> = Vec::new();
+    for (i, header) in headers.iter().enumerate() {
+        if let Some(prefix) = header.strip_suffix(" - Value").filter(|p| p.starts_with("Phone")) {
+            let label = column(&[&format!("{prefix} - Label"), &format!("{prefix} - Type")]);
+            phones.push((i, Label::Column(label)));
+        } else if !header.contains(" - ") && header.trim_end_matches([' ', '2', '3']).ends_with("Phone") {
+            let kind = header.trim_end_matches([' ', '2', '3']).trim_end_matches("Phone");
+            phones.push((i, Label::Fixed(normalize_label(kind))));
+        }
+    }
+    if phones.is_empty() {
+        return Err(invalid("no phone number columns — expected a Google Contacts or Outlook CSV export".into()));
+    }
+
+    let mut contacts = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| invalid(e.to_string()))?;
+        let cell = |i: Option<usize>| i.and_then(|i| record.get(i)).map(str::trim).filter(|v| !v.is_empty());
+
+        let name = cell(full_name).map(str::to_string).or_else(|| {
+            let parts: Vec<&str> = [first, middle, last].into_iter().filter_map(cell).collect();
+            (!parts.is_empty()).then(|| parts.join(" "))
+        });
+        let name = name.or_else(|| cell(org).map(str::to_string));
+
+        let mut numbers = Vec::new();
+        for (i, label) in &phones {
+            let Some(value) = cell(Some(*i)) else { continue };
+            let label = match label {
+                Label::Column(column) => cell(*column).and_then(normalize_label),
+                Label::Fixed(label) => label.clone(),
+            };
+            for number in value.split(":::").map(str::trim).filter(|n| !n.is_empty()) {
+                numbers.push((label.clone(), number.to_string()));
+            }
+        }
+
+        let org = cell(org).filter(|org| name.as_deref() != Some(*org));
+        let notes: Vec<&str> = org.into_iter().chain(cell(notes)).collect();
+        contacts.push(Imported {
+            source: format!("row {}", record.position().map_or(0, |p| p.line())),
+            name,
+            numbers,
+            notes: (!notes.is_empty()).then(|| notes.join(" · ")),
+        });
+    }
+    Ok(contacts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labelled(contact: &Imported) -> Vec<(Option<&str>, &str)> {
+        contact.numbers.iter().map(|(l, n)| (l.as_deref(), n.as_str())).collect()
+    }
+
+    #[test]
+    fn reads_google_exports() {
+        let text = "\
+First Name,Middle Name,Last Name,Organization Name,Notes,Phone 1 - Label,Phone 1 - Value,Phone 2 - Label,Phone 2 - Value
+Alice,,Smith,ACME,\"Front desk, ask for Bob
+Weekdays only\",* Mobile,+1 415 555 0100 ::: +1 415 555 0101,Work,+1 415 555 0102
+,,,Pizza Place,,Home,020 7946 0018,,
+";
+        let read = parse(text).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].name.as_deref(), Some("Alice Smith"));
+        assert_eq!(read[0].notes.as_deref(), Some("ACME · Front desk, ask for Bob\nWeekdays only"));
+        assert_eq!(
+            labelled(&read[0]),
+            [(Some("mobile"), "+1 415 555 0100"), (Some("mobile"), "+1 415 555 0101"), (Some("work"), "+1 415 555 0102")]
+        );
+        assert_eq!(read[0].source, "row 2");
+        assert_eq!(read[1].name.as_deref(), Some("Pizza Place"));
+        assert_eq!(read[1].notes, None);
+        assert_eq!(labelled(&read[1]), [(Some("home"), "020 7946 0018")]);
+        assert_eq!(read[1].source, "row 4");
+    }
+
+    #[test]
+    fn reads_older_google_exports() {
+        let text = "Name,Given Name,Family Name,Phone 1 - Type,Phone 1 - Value\nDr. Jane Doe,Jane,Doe,Other,+14155550100\n";
+        let read = parse(text).unwrap();
+        assert_eq!(read[0].name.as_deref(), Some("Dr. Jane Doe"));
+        assert_eq!(labelled(&read[0]), [(None, "+14155550100")]);
+    }
+
+    #[test]
+    fn reads_outlook_exports() {
+        let text = "\
+First Name,Last Name,Company,Business Phone,Business Phone 2,Mobile Phone,Home Fax,Primary Phone
+Bob,Jones,Initech,(415) 555-0100,(415) 555-0101,,,+1 415 555 0102
+";
+        let read = parse(text).unwrap();
+        assert_eq!(read[0].name.as_deref(), Some("Bob Jones"));
+        assert_eq!(read[0].notes.as_deref(), Some("Initech"));
+        assert_eq!(
+            labelled(&read[0]),
+            [(Some("work"), "(415) 555-0100"), (Some("work"), "(415) 555-0101"), (None, "+1 415 555 0102")]
+        );
+    }
+
+    #[test]
+    fn keeps_rows_without_a_name_or_number() {
+        let text = "First Name,Phone 1 - Value\n,+14155550100\nCarol\n";
+        let read = parse(text).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].name, None);
+        assert_eq!(read[1].name.as_deref(), Some("Carol"));
+        assert!(read[1].numbers.is_empty());
+    }
+
+    #[test]
+    fn rejects_files_without_phone_columns() {
+        for text in ["", "First Name,Last Name,E-mail Address\nAlice,Smith,alice@example.com\n", "BEGIN:VCARD\n"] {
+            let err = parse(text).unwrap_err();
+            assert!(matches!(err, DialError::InvalidImport(ref m) if m.contains("no phone number columns")), "{text:?}: {err}");
+        }
+    }
+}
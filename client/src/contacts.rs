@@ -0,0 +1,233 @@
+//! Local address book: `contacts.toml`, next to `config.toml`.
+//!
+//! ```toml
+//! [[contact]]
+//! name = "Alice Smith"
+//! notes = "Front desk, ask for ext. 12"
+//!
+//! [[contact.number]]
+//! label = "mobile"
+//! number = "+14155550100"
+//! ```
+//!
+//! Numbers are stored in E.164, as `dial contacts add` normalised them, so
+//! the file stays valid if `default_region` changes later.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::errors::DialError;
+
+/// Contents of `contacts.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Contacts {
+    #[serde(default, rename = "contact")]
+    pub contacts: Vec<Contact>,
+}
+
+/// One person, with every number they can be reached on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: String,
+
+    /// Free text shown by `dial contacts list`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    #[serde(default, rename = "number")]
+    pub numbers: Vec<ContactNumber>,
+}
+
+/// A number and what it is ("mobile", "work", …).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactNumber {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+
+    /// E.164, then any keys to press once connected (`+14155550100,12`)
+    pub number: String,
+}
+
+impl ContactNumber {
+    /// The label, or the number itself when there is none.
+    pub fn label_or_number(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.number)
+    }
+}
+
+/// A contact as another address book exported it: numbers as written
+/// there, not yet normalised.
+#[derive(Debug, Default)]
+pub struct Imported {
+    /// Where in the file it was found ("card 3", "row 12"), for reports
+    pub source: String,
+    pub name: Option<String>,
+    /// `(label, number)` pairs
+    pub numbers: Vec<(Option<String>, String)>,
+    pub notes: Option<String>,
+}
+
+impl Contacts {
+    /// Returns the path to the contacts file.
+    pub fn path() -> PathBuf {
+        Config::path().with_file_name("contacts.toml")
+    }
+
+    /// Load the contacts file; an empty book if there is none yet.
+    pub fn load() -> Result<Self, DialError> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path)?;
+        toml::from_str(&raw).map_err(|e| DialError::ContactsParse {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Write the book back, creating parent directories if needed.
+    pub fn save(&self) -> Result<(), DialError> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let toml_str = toml::to_string_pretty(self).expect("contacts must serialise");
+        fs::write(&path, toml_str)?;
+        Ok(())
+    }
+
+    /// The contact `query` names: an exact (case-insensitive) name, else the
+    /// only name containing `query`.
+    pub fn find(&self, query: &str) -> Result<&Contact, DialError> {
+        self.position(query).map(|i| &self.contacts[i])
+    }
+
+    /// Mutable [`Contacts::find`].
+    pub fn find_mut(&mut self, query: &str) -> Result<&mut Contact, DialError> {
+        self.position(query).map(|i| &mut self.contacts[i])
+    }
+
+    /// The contact named exactly `name` (any case), added if missing.
+    pub fn entry(&mut self, name: &str) -> &mut Contact {
+        let name = name.trim();
+        let index = match self.contacts.iter().position(|c| fold(&c.name) == fold(name)) {
+            Some(index) => index,
+            None => {
+                self.contacts.push(Contact { name: name.to_string(), notes: None, numbers: Vec::new() });
+                self.contacts.sort_by_key(|c| fold(&c.name));
+                self.contacts.iter().position(|c| c.name == name).expect("just added")
+            }
+        };
+        &mut self.contacts[index]
+    }
+
+    /// Whether a contact is named exactly `name` (any case).
+    pub fn contains(&self, name: &str) -> bool {
+        self.contacts.iter().any(|c| fold(&c.name) == fold(name))
+    }
+
+    /// The contact `number` (E.164) is saved for.
+    pub fn owner_of(&self, number: &str) -> Option<&Contact> {
+        self.contacts.iter().find(|c| c.numbers.iter().any(|n| n.number == number))
+    }
+
+    /// Remove and return the contact `query` names.
+    pub fn remove(&mut self, query: &str) -> Result<Contact, DialError> {
+        self.position(query).map(|i| self.contacts.remove(i))
+    }
+
+    fn position(&self, query: &str) -> Result<usize, DialError> {
+        let query = query.trim();
+        let needle = fold(query);
+        if let Some(index) = self.contacts.iter().position(|c| fold(&c.name) == needle) {
+            return Ok(index);
+        }
+        let matches: Vec<usize> = (0..self.contacts.len())
+            .filter(|&i| fold(&self.contacts[i].name).contains(&needle))
+            .collect();
+        match matches[..] {
+            [index] => Ok(index),
+            [] => Err(DialError::UnknownContact(query.to_string())),
+            _ => Err(DialError::AmbiguousContact {
+                query: query.to_string(),
+                names: matches.iter().map(|&i| self.contacts[i].name.clone()).collect(),
+            }),
+        }
+    }
+}
+
+/// A name as every lookup compares it: trimmed, in any case.
+fn fold(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Whether `input` may name a contact when it doesn't spell a number: it
+/// has a letter in it (`3M Support`, `911 Dispatch`) and isn't written as
+/// an international number.
+pub fn is_name(input: &str) -> bool {
+    !input.trim_start().starts_with('+') && input.chars().any(char::is_alphabetic)
+}
+
+/// The label `dial` stores for a phone type another address book wrote:
+/// vCard `TYPE`s (`CELL`, `WORK`), Google labels (`* Mobile`) or Apple's
+/// `_$!<Mobile>!$_`.  `None` for types that say nothing (`VOICE`, `PREF`).
+pub fn normalize_label(raw: &str) -> Option<String> {
+    let label = raw.trim().trim_start_matches('*').trim();
+    let label = label.strip_prefix("_$!<").and_then(|l| l.strip_suffix(">!$_")).unwrap_or(label);
+    let label = label.to_lowercase();
+    let label = match label.as_str() {
+        "" | "voice" | "pref" | "primary" | "other" => return None,
+        "cell" | "mobile" | "iphone" | "cellular" => "mobile",
+        "work" | "business" => "work",
+        "home" | "private" => "home",
+        "fax" | "work fax" | "home fax" | "other fax" => "fax",
+        other => other,
+    };
+    Some(label.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(names: &[&str]) -> Contacts {
+        let mut book = Contacts::default();
+        for name in names {
+            book.entry(name);
+        }
+        book
+    }
+
+    #[test]
+    fn names_compare_in_any_case() {
+        let mut book = book(&["Zoë Martin", "bob"]);
+        assert!(book.contains(" ZOË MARTIN "));
+        assert_eq!(book.find("zoë").unwrap().name, "Zoë Martin");
+        book.entry("ZOË MARTIN");
+        book.entry("Adam");
+        let names: Vec<&str> = book.contacts.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Adam", "bob", "Zoë Martin"]);
+    }
+
+    #[test]
+    fn exact_names_win_over_partial_matches() {
+        let book = book(&["Smith, John", "Smith, Jane", "3M Support", "3M Support Desk"]);
+        assert_eq!(book.find("3m support").unwrap().name, "3M Support");
+        assert!(matches!(book.find("Smith"), Err(DialError::AmbiguousContact { .. })));
+        assert!(matches!(book.find("Bob"), Err(DialError::UnknownContact(_))));
+    }
+
+    #[test]
+    fn names_may_start_with_digits() {
+        for name in ["alice", "3M Support", "911 Dispatch", "Smith, John"] {
+            assert!(is_name(name), "{name}");
+        }
+        for number in ["+1 415 CALL", "020 7946 0018", "*123#"] {
+            assert!(!is_name(number), "{number}");
+        }
+    }
+}
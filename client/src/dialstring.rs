@@ -0,0 +1,241 @@
+//! Dial strings: a phone number followed by keys to press once the call is answered.
+//!
+//! `+14155550100,,,1234#` dials `+14155550100`, waits three pauses, then
+//! presses `1234#` — the usual way to reach an extension or a conference PIN.
+//! Besides digits, `*`, `#` and `A`–`D`, the post-dial part may contain
+//!
+//! * `,` (also `p`) — pause for [`PAUSE`]
+//! * `;` (also `w`) — wait until the user presses Enter
+//!
+//! and `x` / `ext` is read as a pause, so `+1 415 555 0100 x1234` works too.
+//! The number itself may be written in any form [`crate::phone`] reads.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::errors::DialError;
+use crate::phone::{self, Region};
+
+/// How long each `,` in a dial string waits.
+pub const PAUSE: Duration = Duration::from_secs(2);
+
+/// One step after the call connects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostDial {
+    Pause,
+    Wait,
+    /// DTMF keys, pressed in one go
+    Tones(String),
+}
+
+/// A parsed dial string: the number to call and what to do once connected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialString {
+    /// The number as typed, before normalisation
+    pub typed: String,
+    /// E.164 number, separators removed
+    pub number: String,
+    pub post_dial: Vec<PostDial>,
+}
+
+impl DialString {
+    /// Parse `input`, reading the number in `region` (see
+    /// [`phone::normalize`]).
+    pub fn parse(input: &str, region: Option<&Region>) -> Result<Self, DialError> {
+        let (base, rest) = split(input);
+        let number = phone::normalize(base, region).map_err(|e| match e {
+            DialError::InvalidPhoneNumber(_) => DialError::InvalidPhoneNumber(input.to_string()),
+            e => e,
+        })?;
+        let post_dial = parse_post_dial(input, rest)?;
+        Ok(Self { typed: base.trim().to_string(), number, post_dial })
+    }
+
+    /// Check `input` before the region is known: the post-dial steps in
+    /// full, the number as far as [`phone::check`] can.
+    pub fn check(input: &str) -> Result<(), DialError> {
+        let (base, rest) = split(input);
+        phone::check(base).map_err(|_| DialError::InvalidPhoneNumber(input.to_string()))?;
+        parse_post_dial(input, rest).map(|_| ())
+    }
+
+    /// `pause 6s → 1234#`, or `None` without post-dial steps.
+    pub fn describe_post_dial(&self) -> Option<String> {
+        if self.post_dial.is_empty() {
+            return None;
+        }
+        let mut parts: Vec<String> = Vec::new();
+        let mut pauses = 0u64;
+        for step in &self.post_dial {
+            let part = match step {
+                PostDial::Pause => {
+                    pauses += 1;
+                    continue;
+                }
+                PostDial::Wait => "wait for Enter".to_string(),
+                PostDial::Tones(keys) => keys.clone(),
+            };
+            if pauses > 0 {
+                parts.push(format!("pause {}s", PAUSE.as_secs() * pauses));
+                pauses = 0;
+            }
+            parts.push(part);
+        }
+        Some(parts.join(" → "))
+    }
+}
+
+impl fmt::Display for DialString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.number)?;
+        for step in &self.post_dial {
+            match step {
+                PostDial::Pause => f.write_str(",")?,
+                PostDial::Wait => f.write_str(";")?,
+                PostDial::Tones(keys) => f.write_str(keys)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `input` split into the number and what follows it.
+fn split(input: &str) -> (&str, &str) {
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| {
+            !(c.is_ascii_digit() || matches!(c, '+' | ' ' | '\u{a0}' | '-' | '.' | '/' | '(' | ')'))
+        })
+        .unwrap_or(trimmed.len());
+    trimmed.split_at(split)
+}
+
+/// The steps in `rest`, the part of `input` after the number.
+fn parse_post_dial(input: &str, rest: &str) -> Result<Vec<PostDial>, DialError> {
+    let invalid = |c: char| DialError::InvalidDialString {
+        input: input.to_string(),
+        reason: format!("'{c}' is not a pause (, ;) or DTMF key (0-9 * # A-D)"),
+    };
+    let mut post_dial = Vec::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        let step = match c.to_ascii_uppercase() {
+            ' ' | '-' | '.' => continue,
+            ',' | 'P' | 'X' => PostDial::Pause,
+            ';' | 'W' => PostDial::Wait,
+            // "ext" / "ext." — read like 'x'
+            'E' if next_is(&chars, "XT") => {
+                chars.next();
+                chars.next();
+                PostDial::Pause
+            }
+            k @ ('0'..='9' | '*' | '#' | 'A'..='D') => {
+                if let Some(PostDial::Tones(keys)) = post_dial.last_mut() {
+                    keys.push(k);
+                    continue;
+                }
+                PostDial::Tones(k.to_string())
+            }
+            _ => return Err(invalid(c)),
+        };
+        post_dial.push(step);
+    }
+
+    // A trailing pause or wait would only delay returning
+    while matches!(post_dial.last(), Some(PostDial::Pause | PostDial::Wait)) {
+        post_dial.pop();
+    }
+    Ok(post_dial)
+}
+
+/// Whether the unread input starts with `s` (case-insensitive).
+fn next_is(chars: &std::iter::Peekable<std::str::Chars<'_>>, s: &str) -> bool {
+    chars.clone().take(s.len()).map(|c| c.to_ascii_uppercase()).eq(s.chars())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PostDial::{Pause, Tones, Wait};
+
+    fn parse(input: &str) -> DialString {
+        DialString::parse(input, None).unwrap()
+    }
+
+    #[test]
+    fn plain_numbers_have_no_post_dial() {
+        let dial = parse(" +1 (415) 555-0100 ");
+        assert_eq!(dial.number, "+14155550100");
+        assert_eq!(dial.typed, "+1 (415) 555-0100");
+        assert!(dial.post_dial.is_empty());
+        assert_eq!(dial.describe_post_dial(), None);
+    }
+
+    #[test]
+    fn pauses_and_keys_follow_the_number() {
+        let dial = parse("+14155550100,,,1234#");
+        assert_eq!(dial.number, "+14155550100");
+        assert_eq!(dial.post_dial, [Pause, Pause, Pause, Tones("1234#".into())]);
+        assert_eq!(dial.to_string(), "+14155550100,,,1234#");
+        assert_eq!(dial.describe_post_dial().as_deref(), Some("pause 6s → 1234#"));
+    }
+
+    #[test]
+    fn waits_and_letter_forms() {
+        let dial = parse("+14155550100;99p*a");
+        assert_eq!(dial.post_dial, [Wait, Tones("99".into()), Pause, Tones("*A".into())]);
+        assert_eq!(parse("+14155550100w1").post_dial, [Wait, Tones("1".into())]);
+        assert_eq!(
+            parse("+14155550100;1").describe_post_dial().as_deref(),
+            Some("wait for Enter → 1")
+        );
+    }
+
+    #[test]
+    fn extensions_read_as_a_pause() {
+        let expected = [Pause, Tones("1234".into())];
+        assert_eq!(parse("+1 415 555 0100 x1234").post_dial, expected);
+        assert_eq!(parse("+1 415 555 0100 ext. 1234").post_dial, expected);
+        assert_eq!(parse("+1 415 555 0100 EXT 1234").post_dial, expected);
+    }
+
+    #[test]
+    fn trailing_pauses_are_dropped() {
+        assert_eq!(parse("+14155550100,,;").post_dial, []);
+        assert_eq!(parse("+14155550100,1,,").to_string(), "+14155550100,1");
+    }
+
+    #[test]
+    fn numbers_are_read_in_the_region() {
+        let us = phone::region("US").unwrap();
+        let dial = DialString::parse("(415) 555-0100;99", Some(us)).unwrap();
+        assert_eq!(dial.typed, "(415) 555-0100");
+        assert_eq!(dial.number, "+14155550100");
+        assert_eq!(dial.post_dial, [Wait, Tones("99".into())]);
+    }
+
+    #[test]
+    fn national_numbers_wait_for_the_region() {
+        assert!(DialString::check("(415) 555-0100,,12").is_ok());
+        let err = DialString::parse("(415) 555-0100,,12", None).unwrap_err();
+        assert!(matches!(err, DialError::NoCountryCode(ref n) if n == "(415) 555-0100"), "{err}");
+        let err = DialString::check("(415) 555-0100,1z").unwrap_err();
+        assert!(matches!(err, DialError::InvalidDialString { .. }), "{err}");
+    }
+
+    #[test]
+    fn rejects_unknown_post_dial_characters() {
+        for input in ["+14155550100,1234z", "+14155550100 e1", "+14155550100,#?"] {
+            let err = DialString::parse(input, None).unwrap_err();
+            assert!(matches!(err, DialError::InvalidDialString { .. }), "{input}: {err}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_numbers() {
+        for input in ["", ",,1234", "+1 415", "+1234567890123456,1"] {
+            let err = DialString::parse(input, None).unwrap_err();
+            assert!(matches!(err, DialError::InvalidPhoneNumber(_)), "{input}: {err}");
+        }
+    }
+}
@@ -10,7 +10,8 @@ const SERVICE_TYPE: &str = "_phoneconnect._tcp.local.";
 
 #[derive(Debug, Clone)]
 pub struct DiscoveredGateway {
-    /// Ready-to-use HTTP base URL: "http://10.0.0.5:3000"
+    /// Ready-to-use base URL: "http://10.0.0.5:3000", or "https://…" when the
+    /// gateway advertises TLS
     pub url:  String,
     pub host: String,
     pub port: u16,
@@ -71,7 +72,12 @@ fn discover_blocking(timeout: Duration) -> Option<DiscoveredGateway> {
 
                 let host = addr.to_string();
                 let port = info.get_port();
-                let url  = format!("http://{}:{}", host, port);
+                // The gateway advertises `protocol=wss` when it serves TLS
+                let scheme = match info.get_property_val_str("protocol") {
+                    Some("wss") => "https",
+                    _ => "http",
+                };
+                let url  = format!("{}://{}:{}", scheme, host, port);
 
                 break Some(DiscoveredGateway { url, host, port });
             }
@@ -1,5 +1,24 @@
+use std::time::Duration;
+
+use serde::Deserialize;
 use thiserror::Error;
 
+/// One entry of the `details` array the gateway returns with a 400
+/// `Validation failed` response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub msg: String,
+}
+
+/// Render field errors as an indented bullet list, one per line.
+fn format_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("\n  • {}: {}", e.field, e.msg))
+        .collect()
+}
+
 #[derive(Debug, Error)]
 pub enum DialError {
     // ── Config ────────────────────────────────────────────────────────────────
@@ -12,27 +31,250 @@ pub enum DialError {
     #[error("Failed to parse config file: {0}")]
     ConfigParse(#[from] toml::de::Error),
 
+    #[error("Invalid TLS settings: {0}")]
+    TlsConfig(String),
+
+    #[error("Invalid transport settings: {0}")]
+    Transport(String),
+
     // ── Validation ────────────────────────────────────────────────────────────
     #[error("Invalid phone number '{0}'. Use E.164 format, e.g. +919876543210")]
     InvalidPhoneNumber(String),
 
+    #[error("Invalid dial string '{input}': {reason}")]
+    InvalidDialString { input: String, reason: String },
+
+    #[error("Invalid DTMF digits '{0}'. Use 0-9, *, # and A-D (at most 64)")]
+    InvalidDtmf(String),
+
+    #[error("Invalid USSD code '{0}'. It must start with * or #, end with #, e.g. *123#")]
+    InvalidUssd(String),
+
+    #[error("Invalid SIM '{0}'. Use a slot number (1, 2) or the SIM's label from `dial devices`")]
+    InvalidSim(String),
+
+    #[error("Invalid caller ID prefix '{0}'. Use the carrier's code, e.g. #31# or *67")]
+    InvalidCallerIdPrefix(String),
+
+    #[error("Unknown default_region '{region}'. Use a country code: {}", .known.join(" "))]
+    UnknownRegion { region: String, known: Vec<&'static str> },
+
+    #[error("'{0}' has no country code. Write it with +, e.g. +14155550100, or set default_region in the config")]
+    NoCountryCode(String),
+
     #[error("Device ID must not be empty")]
     EmptyDeviceId,
 
+    #[error("'{0}' is a phone number, not a device ID. Give the device first (dial call <device> <number>) or leave it out")]
+    NumberAsDevice(String),
+
+    #[error("No devices are connected to the gateway")]
+    NoDevicesConnected,
+
+    #[error("Several devices are connected ({}) — name one", .devices.join(", "))]
+    AmbiguousDevice { devices: Vec<String> },
+
+    #[error("Device '{device_id}' has no SIM '{sim}' (it reports: {})", .available.join(", "))]
+    UnknownSim { device_id: String, sim: String, available: Vec<String> },
+
     // ── API ───────────────────────────────────────────────────────────────────
     #[error("HTTP request failed: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(reqwest::Error),
+
+    #[error(
+        "TLS handshake with the gateway failed: {0}\n\
+         Set `ca_cert` (or pin it with `cert_sha256`) in your config if the gateway uses a self-signed certificate."
+    )]
+    Tls(String),
+
+    #[error(
+        "Gateway certificate does not match the pinned fingerprint!\n  \
+         pinned  : {expected}\n  \
+         received: {actual}\n\
+         Someone may be intercepting the connection. If the gateway's certificate was \
+         renewed, update `cert_sha256` in your config."
+    )]
+    CertificatePinMismatch { expected: String, actual: String },
 
     #[error("Gateway returned {status}: {body}")]
     GatewayError { status: u16, body: String },
 
+    #[error("Gateway rejected the request:{}", format_field_errors(.0))]
+    Validation(Vec<FieldError>),
+
     #[error("Device '{device_id}' is not connected to the gateway")]
     DeviceOffline { device_id: String },
 
     #[error("Unauthorized — check the token in your config file")]
     Unauthorized,
 
+    #[error(
+        "Rate limited by the gateway{}",
+        retry_after.map(|d| format!(" — retry in {} s", d.as_secs().max(1))).unwrap_or_default()
+    )]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error(
+        "Protocol mismatch: dial speaks v{client} but the gateway{} only serves v{min} and later.\n\
+         Upgrade the dial CLI.",
+        gateway_version.as_deref().map(|v| format!(" ({v})")).unwrap_or_default()
+    )]
+    ProtocolMismatch { client: u32, min: u32, gateway_version: Option<String> },
+
+    // ── Delivery (device ACK) ─────────────────────────────────────────────────
+    #[error("Command {command_id} was sent but not acknowledged by the device in {timeout_secs} s")]
+    NotAcknowledged { command_id: String, timeout_secs: u64 },
+
+    #[error("Device '{device_id}' dropped before acknowledging command {command_id}")]
+    DeviceDropped { device_id: String, command_id: String },
+
+    // ── Call lifecycle (`dial call --follow`) ─────────────────────────────────
+    #[error("Call to {number} failed on the device")]
+    CallFailed { number: String },
+
+    #[error("The call to {number} ended before it was answered — post-dial keys not sent ({keys})")]
+    PostDialNotSent { number: String, keys: String },
+
+    #[error("Device '{device_id}' disconnected before reporting the outcome")]
+    DeviceDisconnected { device_id: String },
+
+    #[error("Gateway closed the event stream before the call finished")]
+    EventStreamClosed,
+
+    #[error(
+        "Device '{device_id}' has an active call but could not end it.\n\
+         Grant PhoneConnect the \"Phone\" (answer/end calls) permission on the device."
+    )]
+    HangupFailed { device_id: String },
+
+    #[error("No call is ringing on device '{device_id}'")]
+    NothingRinging { device_id: String },
+
+    #[error("Device '{device_id}' did not answer the call: {reason}")]
+    AnswerFailed { device_id: String, reason: String },
+
+    #[error("Device '{device_id}' did not reject the call: {reason}")]
+    RejectFailed { device_id: String, reason: String },
+
+    #[error("Device '{device_id}' did not play the tones: {reason}")]
+    DtmfNotPlayed { device_id: String, reason: String },
+
+    #[error("No answer from {number} within {timeout_secs} s — the calls were not merged")]
+    LegNotAnswered { number: String, timeout_secs: u64 },
+
+    #[error("Device '{device_id}' could not merge the calls: {reason}")]
+    MergeFailed { device_id: String, reason: String },
+
+    #[error("Device '{device_id}' could not {action} the call: {reason}")]
+    ControlNotApplied { device_id: String, action: String, reason: String },
+
+    // ── SMS ───────────────────────────────────────────────────────────────────
+    #[error("Message to {number} could not be sent: {reason}")]
+    SmsFailed { number: String, reason: String },
+
+    #[error("Message text must not be empty")]
+    EmptyMessage,
+
+    // ── USSD ──────────────────────────────────────────────────────────────────
+    #[error("USSD request {code} failed: {reason}")]
+    UssdFailed { code: String, reason: String },
+
+    #[error("No reply to USSD request {code} within {timeout_secs} s")]
+    UssdTimeout { code: String, timeout_secs: u64 },
+
+    // ── Contacts ──────────────────────────────────────────────────────────────
+    #[error("Failed to parse contacts file {path}: {reason}")]
+    ContactsParse { path: String, reason: String },
+
+    #[error("Could not import contacts: {0}")]
+    InvalidImport(String),
+
+    #[error("Contact name must not be empty")]
+    EmptyContactName,
+
+    #[error("No contact matches '{0}' — add one with `dial contacts add`")]
+    UnknownContact(String),
+
+    #[error("Several contacts match '{query}' ({}) — be more specific", .names.join(", "))]
+    AmbiguousContact { query: String, names: Vec<String> },
+
+    #[error("{name} has no number '{number}'")]
+    UnknownContactNumber { name: String, number: String },
+
+    #[error("{0} has no number to dial — add one with `dial contacts add`")]
+    ContactWithoutNumber(String),
+
+    #[error("{name} has several numbers ({}) — run in a terminal to pick one, or dial the number", .labels.join(", "))]
+    NumberNotChosen { name: String, labels: Vec<String> },
+
+    // ── Picker (`dial call` without a number, `dial extract`) ─────────────────
+    #[error("No number given — name one, or run `dial call` in a terminal to pick from contacts and recent calls")]
+    NoNumber,
+
+    #[error("No contacts or recent calls to pick from — give a number, or add contacts with `dial contacts add`")]
+    NothingToPick,
+
+    #[error("No phone number found in the input")]
+    NoPhoneNumberFound,
+
+    #[error("Found several phone numbers ({}) — run in a terminal to pick one, or pass the number", .found.join(", "))]
+    SeveralNumbersFound { found: Vec<String> },
+
+    #[error("Terminal prompt failed: {0}")]
+    Prompt(String),
+
     // ── Bluetooth ─────────────────────────────────────────────────────────────
+    #[allow(dead_code)]
     #[error("Bluetooth error: {0}")]
     Bluetooth(String),
 }
+
+impl From<reqwest::Error> for DialError {
+    fn from(e: reqwest::Error) -> Self {
+        crate::tls::classify(&e).unwrap_or(DialError::Http(e))
+    }
+}
+
+impl DialError {
+    /// Whether retrying the same request may succeed: connection failures,
+    /// timeouts and 5xx responses.  Everything else is a definitive answer.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            DialError::Http(e) => e.is_connect() || e.is_timeout(),
+            DialError::GatewayError { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Process exit code for this error, so scripts can branch on the outcome.
+    ///
+    /// | code | meaning                                   |
+    /// |------|-------------------------------------------|
+    /// | 1    | any other error                           |
+    /// | 3    | the device reported the call (or SMS /    |
+    /// |      | USSD request, merge) as failed            |
+    /// | 4    | the device is offline or dropped mid-call |
+    /// |      | (or no device is connected at all)        |
+    /// | 5    | the outcome (call state, USSD reply, an   |
+    /// |      | added party answering) wasn't observed    |
+    /// | 6    | the device never acknowledged the command |
+    /// | 7    | rate limited by the gateway               |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DialError::CallFailed { .. }
+            | DialError::MergeFailed { .. }
+            | DialError::SmsFailed { .. }
+            | DialError::UssdFailed { .. } => 3,
+            DialError::DeviceOffline { .. }
+            | DialError::NoDevicesConnected
+            | DialError::DeviceDisconnected { .. }
+            | DialError::DeviceDropped { .. } => 4,
+            DialError::EventStreamClosed
+            | DialError::LegNotAnswered { .. }
+            | DialError::UssdTimeout { .. } => 5,
+            DialError::NotAcknowledged { .. } => 6,
+            DialError::RateLimited { .. } => 7,
+            _ => 1,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use async_trait::async_trait;
+use colored::Colorize;
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DialError;
+
+// ── Event types ───────────────────────────────────────────────────────────────
+
+/// Call lifecycle state reported by the Android app in a `STATUS` message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum CallState {
+    /// The phone went off-hook: dialling has begun
+    #[serde(rename = "CALL_STARTED")]
+    Started,
+    /// The far end picked up and the call is connected
+    #[serde(rename = "CALL_ANSWERED")]
+    Answered,
+    #[serde(rename = "CALL_ENDED")]
+    Ended,
+    #[serde(rename = "CALL_FAILED")]
+    Failed,
+}
+
+impl CallState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CallState::Started  => "CALL_STARTED",
+            CallState::Answered => "CALL_ANSWERED",
+            CallState::Ended    => "CALL_ENDED",
+            CallState::Failed   => "CALL_FAILED",
+        }
+    }
+}
+
+/// Progress of an SMS reported by the Android app in an `SMS_STATUS` message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SmsState {
+    #[serde(rename = "SMS_SENT")]
+    Sent,
+    #[serde(rename = "SMS_DELIVERED")]
+    Delivered,
+    #[serde(rename = "SMS_FAILED")]
+    Failed,
+}
+
+impl SmsState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SmsState::Sent      => "SMS_SENT",
+            SmsState::Delivered => "SMS_DELIVERED",
+            SmsState::Failed    => "SMS_FAILED",
+        }
+    }
+}
+
+/// State of one call leg reported in a `LEG_STATUS` message, when a second
+/// party is added or calls are merged (`dial conference`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LegState {
+    Dialing,
+    Ringing,
+    Active,
+    Holding,
+    Disconnected,
+}
+
+impl LegState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LegState::Dialing      => "DIALING",
+            LegState::Ringing      => "RINGING",
+            LegState::Active       => "ACTIVE",
+            LegState::Holding      => "HOLDING",
+            LegState::Disconnected => "DISCONNECTED",
+        }
+    }
+}
+
+/// Progress of a call ringing on a device, reported in an `INCOMING` message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomingState {
+    Ringing,
+    Answered,
+    Missed,
+}
+
+impl IncomingState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncomingState::Ringing  => "RINGING",
+            IncomingState::Answered => "ANSWERED",
+            IncomingState::Missed   => "MISSED",
+        }
+    }
+}
+
+/// One event received from the gateway's `GET /events` stream.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GatewayEvent {
+    /// Call lifecycle update forwarded from a device.
+    Status {
+        state: CallState,
+        /// Id of the CALL command this update belongs to (newer app versions only)
+        #[serde(rename = "commandId")]
+        command_id: Option<String>,
+    },
+    /// SMS progress forwarded from a device.
+    SmsStatus {
+        state: SmsState,
+        /// Id of the SMS command this update belongs to
+        #[serde(rename = "commandId")]
+        command_id: Option<String>,
+        /// Why sending failed (SMS_FAILED only)
+        reason: Option<String>,
+    },
+    /// Carrier reply to a USSD request, forwarded from a device.
+    UssdResponse {
+        /// Id of the USSD command this reply belongs to
+        #[serde(rename = "commandId")]
+        command_id: Option<String>,
+        /// `false` when the network rejected the request
+        ok: bool,
+        /// Reply text, or why the request failed
+        message: String,
+    },
+    /// One call's state while several are up, forwarded from a device.
+    LegStatus {
+        /// Number of the other party (may carry a caller-ID prefix)
+        number: String,
+        state: LegState,
+        /// Part of a merged conference
+        #[serde(default)]
+        conference: bool,
+        /// Id of the CALL command that placed this leg, when known
+        #[serde(rename = "commandId")]
+        command_id: Option<String>,
+    },
+    /// A call ringing on a device, forwarded from that device.
+    Incoming {
+        /// Device the call rings on (streams may cover every device)
+        #[serde(rename = "deviceId")]
+        device_id: String,
+        state: IncomingState,
+        /// Caller's number; `None` when withheld
+        number: Option<String>,
+        /// Contact name the phone shows for the caller, if any
+        name: Option<String>,
+        /// When the gateway received the report (RFC 3339)
+        timestamp: Option<String>,
+    },
+    /// The device's WebSocket closed or timed out.
+    Disconnected {
+        reason: Option<String>,
+    },
+    /// Event types this version of `dial` does not know about.
+    #[serde(other)]
+    Unknown,
+}
+
+// ── Event sources ─────────────────────────────────────────────────────────────
+
+/// A live subscription to gateway events, independent of the transport.
+#[async_trait]
+pub trait EventSource: Send {
+    /// Wait for the next event.  Returns `Ok(None)` once the gateway closes
+    /// the stream.
+    async fn next(&mut self) -> Result<Option<GatewayEvent>, DialError>;
+}
+
+/// What [`crate::gateway::Gateway::events`] hands back.
+pub type EventStream = Box<dyn EventSource>;
+
+// ── Server-Sent Events reader ─────────────────────────────────────────────────
+
+/// Incremental reader over a `text/event-stream` response.
+///
+/// Only `data:` fields are interpreted — the gateway puts the full event
+/// (including its `type`) into a single JSON data line.  Comment lines such as
+/// the periodic `: keepalive` are skipped.
+pub struct SseStream {
+    response: Response,
+    /// Raw bytes received so far; decoded only once a full event has arrived
+    /// so multi-byte characters split across chunks stay intact.
+    buf: Vec<u8>,
+}
+
+impl SseStream {
+    pub fn new(response: Response) -> Self {
+        Self { response, buf: Vec::new() }
+    }
+}
+
+#[async_trait]
+impl EventSource for SseStream {
+    async fn next(&mut self) -> Result<Option<GatewayEvent>, DialError> {
+        loop {
+            // Dispatch any complete event already sitting in the buffer
+            if let Some(end) = self.buf.windows(2).position(|w| w == b"\n\n") {
+                let block: Vec<u8> = self.buf.drain(..end + 2).collect();
+                let data = String::from_utf8_lossy(&block)
+                    .lines()
+                    .filter_map(|l| l.strip_prefix("data:"))
+                    .map(str::trim_start)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if data.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<GatewayEvent>(&data) {
+                    Ok(event) => return Ok(Some(event)),
+                    Err(e) => {
+                        eprintln!("{} Ignoring malformed gateway event: {e}", "warn:".yellow());
+                        continue;
+                    }
+                }
+            }
+
+            match self.response.chunk().await? {
+                Some(bytes) => self.buf.extend(bytes.iter().filter(|&&b| b != b'\r')),
+                None => return Ok(None),
+            }
+        }
+    }
+}
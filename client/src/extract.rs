@@ -0,0 +1,205 @@
+//! Phone numbers in free text, for `dial extract`.
+
+use crate::phone::{self, Region};
+
+/// A phone number [`extract_phones`] found in free text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundNumber {
+    /// The text as it appeared, e.g. `(415) 555-0100 x12`
+    pub found: String,
+    /// E.164
+    pub number: String,
+    /// Extension to press once the call is up
+    pub extension: Option<String>,
+}
+
+impl FoundNumber {
+    /// What `dial call` dials: the number, then a pause and the extension.
+    pub fn dial_string(&self) -> String {
+        match &self.extension {
+            Some(ext) => format!("{},{ext}", self.number),
+            None => self.number.clone(),
+        }
+    }
+}
+
+/// Characters written between the digits of a phone number.
+fn is_phone_separator(c: char) -> bool {
+    matches!(c, ' ' | '\u{a0}' | '-' | '\u{2013}' | '.' | '/' | '(' | ')')
+}
+
+/// Phone numbers in free text (an email signature, a web page snippet), in
+/// order of appearance and without repeats: `tel:` links, and runs of
+/// digits written with the usual separators, with an extension (`x12`,
+/// `ext. 12`) if one follows.  Numbers without `+` are read in `region`;
+/// runs [`phone::normalize`] rejects and dates are skipped.
+pub fn extract_phones(text: &str, region: Option<&Region>) -> Vec<FoundNumber> {
+    let mut found: Vec<FoundNumber> = Vec::new();
+    let mut add = |candidate: FoundNumber| {
+        if !found.iter().any(|f| f.number == candidate.number && f.extension == candidate.extension) {
+            found.push(candidate);
+        }
+    };
+
+    let mut pos = 0;
+    let mut prev: Option<char> = None;
+    while let Some(c) = text[pos..].chars().next() {
+        let rest = &text[pos..];
+
+        // ── tel:+1-415-555-0100;ext=12 ──
+        if rest.get(..4).is_some_and(|p| p.eq_ignore_ascii_case("tel:")) {
+            let len = rest.find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>')).unwrap_or(rest.len());
+            let uri = rest[4..len].replace("%20", " ");
+            let mut parts = uri.split(';');
+            let number = parts.next().unwrap_or_default();
+            let extension = parts
+                .find_map(|p| p.strip_prefix("ext="))
+                .filter(|e| !e.is_empty() && e.chars().all(|c| c.is_ascii_digit()))
+                .map(str::to_string);
+            if let Ok(number) = phone::normalize(number, region) {
+                add(FoundNumber { found: rest[..len].to_string(), number, extension });
+            }
+            pos += len;
+            prev = rest[..len].chars().last();
+            continue;
+        }
+
+        // ── +44 (0)20 7946 0018, (415) 555-0100 x12 ──
+        let starts_run = match c {
+            '+' | '(' => rest[1..].trim_start_matches(' ').starts_with(|d: char| d.is_ascii_digit()),
+            d => d.is_ascii_digit(),
+        };
+        // Digits inside a word (an order ID, a postcode) are not a number
+        if starts_run && !prev.is_some_and(|p| p.is_alphanumeric()) {
+            let (mut end, mut digits, mut separators) = (0, 0, 0);
+            for (i, d) in rest.char_indices().skip(usize::from(c == '+')) {
+                if d.is_ascii_digit() {
+                    digits += 1;
+                    separators = 0;
+                    end = i + 1;
+                } else if is_phone_separator(d) && separators < 2 {
+                    separators += 1;
+                } else {
+                    break;
+                }
+            }
+            let run = &rest[..end];
+            let (extension, ext_len) = extension_after(&rest[end..]);
+            if digits <= 15 && !looks_like_date(run) {
+                if let Ok(number) = phone::normalize(run, region) {
+                    let len = end + ext_len;
+                    add(FoundNumber { found: rest[..len].to_string(), number, extension });
+                }
+            }
+            pos += end.max(c.len_utf8());
+            prev = rest[..end.max(c.len_utf8())].chars().last();
+            continue;
+        }
+
+        pos += c.len_utf8();
+        prev = Some(c);
+    }
+    found
+}
+
+/// An extension written after a number (` x12`, ` ext. 12`, `, extension
+/// 12`) and how many bytes of `rest` it takes.
+fn extension_after(rest: &str) -> (Option<String>, usize) {
+    let trimmed = rest.trim_start_matches([' ', ',']);
+    let lower = trimmed.to_ascii_lowercase();
+    let Some(marker) = ["extension", "ext.", "ext", "x"].iter().find(|m| lower.starts_with(*m)) else {
+        return (None, 0);
+    };
+    let after = trimmed[marker.len()..].trim_start_matches([' ', ':', '.']);
+    let ext: String = after.chars().take_while(char::is_ascii_digit).collect();
+    if ext.is_empty() || ext.len() > 6 {
+        return (None, 0);
+    }
+    let len = rest.len() - after.len() + ext.len();
+    (Some(ext), len)
+}
+
+/// `2024-03-14`, `14.03.2024`, `3/14/24`: digits a number scan picks up
+/// but nobody dials.
+fn looks_like_date(run: &str) -> bool {
+    let parts: Vec<&str> = run.split(['-', '.', '/']).collect();
+    let [a, b, c] = parts[..] else { return false };
+    if ![a, b, c].iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    (a.len() == 4 && b.len() <= 2 && c.len() <= 2) || (a.len() <= 2 && b.len() <= 2 && matches!(c.len(), 2 | 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(text: &str, region: Option<&str>) -> Vec<String> {
+        let region = region.map(|code| phone::region(code).unwrap());
+        extract_phones(text, region).iter().map(FoundNumber::dial_string).collect()
+    }
+
+    #[test]
+    fn finds_numbers_across_lines() {
+        let signature = "Jane Doe\nSales, ACME Ltd.\nTel: +44 (0)20 7946 0018\nMobile: +1 415-555-0100\n";
+        assert_eq!(numbers(signature, None), ["+442079460018", "+14155550100"]);
+    }
+
+    #[test]
+    fn keeps_what_was_written() {
+        let found = extract_phones("Call (415) 555-0100 x12 today.", phone::region("US").ok());
+        assert_eq!(
+            found,
+            [FoundNumber {
+                found: "(415) 555-0100 x12".to_string(),
+                number: "+14155550100".to_string(),
+                extension: Some("12".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_extensions() {
+        for text in [
+            "+1 415 555 0100 x12",
+            "+1 415 555 0100 ext. 12",
+            "+1 415 555 0100, extension 12",
+            "+1 415 555 0100 Ext: 12",
+            "<a href=\"tel:+1-415-555-0100;ext=12\">",
+        ] {
+            assert_eq!(numbers(text, None), ["+14155550100,12"], "{text}");
+        }
+        // Seven digits is no extension
+        assert_eq!(numbers("+1 415 555 0100 x1234567", None), ["+14155550100"]);
+    }
+
+    #[test]
+    fn skips_dates() {
+        let text = "Invoice of 2024-03-14, due 14.03.2024 (or 3/14/24). Questions: +1 415 555 0100";
+        assert_eq!(numbers(text, Some("US")), ["+14155550100"]);
+        assert!(looks_like_date("2024-03-14"));
+        assert!(looks_like_date("14.03.2024"));
+        assert!(looks_like_date("3/14/24"));
+        assert!(!looks_like_date("415-555-0100"));
+        assert!(!looks_like_date("020 7946 0018"));
+    }
+
+    #[test]
+    fn lists_each_number_once() {
+        let text = "tel:+14155550100 or +1 (415) 555-0100, ext. 7 at +1.415.555.0100 x7; else 415 555 0100";
+        assert_eq!(numbers(text, Some("US")), ["+14155550100", "+14155550100,7"]);
+    }
+
+    #[test]
+    fn needs_a_region_for_national_numbers() {
+        let text = "Office: 020 7946 0018";
+        assert!(numbers(text, None).is_empty());
+        assert_eq!(numbers(text, Some("GB")), ["+442079460018"]);
+    }
+
+    #[test]
+    fn skips_digits_inside_words_and_short_runs() {
+        let text = "Order AB12345678, room 42, ZIP 94103, ticket INC4155550100";
+        assert_eq!(numbers(text, Some("US")), Vec::<String>::new());
+    }
+}
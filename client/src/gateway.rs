@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+
+use crate::api::{
+    AnswerOutcome, CallSpec, CommandOptions, CommandReceipt, ControlAction, ControlOutcome,
+    DevicesResponse, DtmfOutcome, GatewayClient, HangupOutcome, HealthResponse, MergeOutcome,
+    RejectOutcome,
+};
+use crate::config::Config;
+use crate::errors::DialError;
+use crate::events::EventStream;
+use crate::retry::Retrying;
+
+#[cfg(test)]
+pub mod fake;
+
+// ── Gateway trait ─────────────────────────────────────────────────────────────
+
+/// Everything `dial` can ask of a PhoneConnect gateway.
+///
+/// Command handlers only ever see `&dyn Gateway`, so the transport behind it
+/// (HTTP today) and any wrappers around it (retries, failover, an in-memory
+/// fake) can change without touching the handlers.
+#[async_trait]
+pub trait Gateway: Send + Sync {
+    /// Send a CALL command to `device_id` for `call.number`.
+    async fn call(
+        &self,
+        device_id: &str,
+        call: &CallSpec,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError>;
+
+    /// Send END_CALL to `device_id`.  The receipt's result says whether a
+    /// call was active (only when the device ACKed).
+    async fn hangup(
+        &self,
+        device_id: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<HangupOutcome>, DialError>;
+
+    /// Send ANSWER_CALL to `device_id`.  The receipt's result says whether a
+    /// call was ringing and got picked up (only when the device ACKed).
+    async fn answer(
+        &self,
+        device_id: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<AnswerOutcome>, DialError>;
+
+    /// Send REJECT_CALL to `device_id`, texting `sms` to the caller if given.
+    /// The reply's progress arrives as [`crate::events::GatewayEvent::SmsStatus`]
+    /// tagged with the command's id.
+    async fn reject(
+        &self,
+        device_id: &str,
+        sms: Option<&str>,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<RejectOutcome>, DialError>;
+
+    /// Send an SMS command to `device_id`.  Whether the text was sent and
+    /// delivered arrives later as [`crate::events::GatewayEvent::SmsStatus`].
+    async fn sms(
+        &self,
+        device_id: &str,
+        number: &str,
+        text: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError>;
+
+    /// Play `digits` as DTMF tones into the call connected on `device_id`.
+    /// The receipt's result says whether they were played (only when the
+    /// device ACKed).
+    async fn dtmf(
+        &self,
+        device_id: &str,
+        digits: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<DtmfOutcome>, DialError>;
+
+    /// Merge the calls up on `device_id` into a conference.  The receipt's
+    /// result says whether the device could (only when it ACKed); each leg
+    /// then reports through [`crate::events::GatewayEvent::LegStatus`].
+    async fn merge(
+        &self,
+        device_id: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<MergeOutcome>, DialError>;
+
+    /// Mute, switch the speakerphone or hold the call connected on
+    /// `device_id`.  The receipt's result carries the call's new state (only
+    /// when the device ACKed).
+    async fn control(
+        &self,
+        device_id: &str,
+        action: ControlAction,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<ControlOutcome>, DialError>;
+
+    /// Send a USSD request from `device_id`.  The carrier's reply arrives
+    /// later as [`crate::events::GatewayEvent::UssdResponse`].
+    async fn ussd(
+        &self,
+        device_id: &str,
+        code: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError>;
+
+    /// List all devices currently connected to the gateway.
+    async fn devices(&self) -> Result<DevicesResponse, DialError>;
+
+    /// Check if the gateway is reachable and report its version.
+    async fn health(&self) -> Result<HealthResponse, DialError>;
+
+    /// Subscribe to device events, optionally for one device only.
+    ///
+    /// Open the stream *before* dispatching a command so no status update
+    /// that follows it can be missed.
+    async fn events(&self, device_id: Option<&str>) -> Result<EventStream, DialError>;
+
+    /// Where requests go, for display (e.g. the base URL).
+    fn endpoint(&self) -> &str;
+
+    /// How requests get there, for display (e.g. "direct TCP").
+    fn transport(&self) -> String;
+}
+
+// ── Construction ──────────────────────────────────────────────────────────────
+
+/// Build the gateway stack described by `config`: the HTTP client wrapped in
+/// the configured retry policy.
+pub fn connect(config: &Config) -> Result<Box<dyn Gateway>, DialError> {
+    Ok(Box::new(Retrying::new(GatewayClient::new(config)?, config.retry_policy())))
+}
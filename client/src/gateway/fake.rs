@@ -0,0 +1,174 @@
+//! In-memory [`Gateway`] for tests: answers every request itself, after
+//! handing out a scripted list of errors first.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::api::{
+    AnswerOutcome, CallSpec, CommandOptions, CommandReceipt, ControlAction, ControlOutcome,
+    DevicesResponse, DtmfOutcome, HangupOutcome, HealthResponse, MergeOutcome, RejectOutcome,
+};
+use crate::errors::DialError;
+use crate::events::{EventSource, EventStream, GatewayEvent};
+
+use super::Gateway;
+
+#[derive(Default)]
+pub struct FakeGateway {
+    /// Errors returned, in order, before requests start succeeding
+    failures: Mutex<VecDeque<DialError>>,
+    /// Every request received, by method name
+    requests: Mutex<Vec<&'static str>>,
+}
+
+impl FakeGateway {
+    /// A gateway that fails the first requests with `errors`, then succeeds.
+    pub fn failing(errors: impl IntoIterator<Item = DialError>) -> Self {
+        Self { failures: Mutex::new(errors.into_iter().collect()), ..Self::default() }
+    }
+
+    /// Method names of the requests received so far.
+    pub fn requests(&self) -> Vec<&'static str> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Log `method` and fail it with the next scripted error, if any.
+    fn answer<T>(&self, method: &'static str, ok: impl FnOnce() -> T) -> Result<T, DialError> {
+        self.requests.lock().unwrap().push(method);
+        match self.failures.lock().unwrap().pop_front() {
+            Some(e) => Err(e),
+            None => Ok(ok()),
+        }
+    }
+}
+
+/// A receipt for a command the device acknowledged without a result.
+fn receipt<R>(device_id: &str) -> CommandReceipt<R> {
+    CommandReceipt {
+        device_id: device_id.to_string(),
+        command_id: "fake-command".to_string(),
+        delivered: true,
+        result: None,
+    }
+}
+
+/// An event stream the gateway closes straight away.
+struct Closed;
+
+#[async_trait]
+impl EventSource for Closed {
+    async fn next(&mut self) -> Result<Option<GatewayEvent>, DialError> {
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl Gateway for FakeGateway {
+    async fn call(
+        &self,
+        device_id: &str,
+        _call: &CallSpec,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError> {
+        self.answer("call", || receipt(device_id))
+    }
+
+    async fn hangup(
+        &self,
+        device_id: &str,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt<HangupOutcome>, DialError> {
+        self.answer("hangup", || receipt(device_id))
+    }
+
+    async fn answer(
+        &self,
+        device_id: &str,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt<AnswerOutcome>, DialError> {
+        self.answer("answer", || receipt(device_id))
+    }
+
+    async fn reject(
+        &self,
+        device_id: &str,
+        _sms: Option<&str>,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt<RejectOutcome>, DialError> {
+        self.answer("reject", || receipt(device_id))
+    }
+
+    async fn sms(
+        &self,
+        device_id: &str,
+        _number: &str,
+        _text: &str,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError> {
+        self.answer("sms", || receipt(device_id))
+    }
+
+    async fn dtmf(
+        &self,
+        device_id: &str,
+        _digits: &str,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt<DtmfOutcome>, DialError> {
+        self.answer("dtmf", || receipt(device_id))
+    }
+
+    async fn merge(
+        &self,
+        device_id: &str,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt<MergeOutcome>, DialError> {
+        self.answer("merge", || receipt(device_id))
+    }
+
+    async fn control(
+        &self,
+        device_id: &str,
+        _action: ControlAction,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt<ControlOutcome>, DialError> {
+        self.answer("control", || receipt(device_id))
+    }
+
+    async fn ussd(
+        &self,
+        device_id: &str,
+        _code: &str,
+        _opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError> {
+        self.answer("ussd", || receipt(device_id))
+    }
+
+    async fn devices(&self) -> Result<DevicesResponse, DialError> {
+        self.answer("devices", || DevicesResponse { count: 0, devices: Vec::new() })
+    }
+
+    async fn health(&self) -> Result<HealthResponse, DialError> {
+        self.answer("health", || HealthResponse {
+            status: "ok".to_string(),
+            version: None,
+            protocol_version: Some(crate::api::PROTOCOL_VERSION),
+            min_protocol_version: Some(1),
+            uptime: 0.0,
+            connected_devices: 0,
+        })
+    }
+
+    async fn events(&self, _device_id: Option<&str>) -> Result<EventStream, DialError> {
+        self.answer("events", || Box::new(Closed) as EventStream)
+    }
+
+    fn endpoint(&self) -> &str {
+        "memory://fake"
+    }
+
+    fn transport(&self) -> String {
+        "in-memory fake".to_string()
+    }
+}
@@ -0,0 +1,82 @@
+//! Numbers `dial call` dialled recently, offered first by the picker it
+//! shows when no number is given.
+//!
+//! One JSON object per call in `~/.local/state/phoneconnect/history.jsonl`
+//! (the local data directory on platforms without a state directory).
+//! History is a convenience: failing to read or write it never fails a
+//! command.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The file is cut back to [`KEEP`] entries once it reaches this many.
+const MAX_ENTRIES: usize = 500;
+const KEEP: usize = 250;
+
+/// One dispatched call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    /// RFC 3339, UTC
+    pub timestamp: String,
+    pub device_id: String,
+    /// E.164
+    pub number: String,
+}
+
+/// Returns the path to the history file.
+pub fn path() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("phoneconnect")
+        .join("history.jsonl")
+}
+
+/// Note a call to `number` from `device_id`.
+pub fn record(device_id: &str, number: &str) {
+    let entry = Entry {
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        device_id: device_id.to_string(),
+        number: number.to_string(),
+    };
+    let line = serde_json::to_string(&entry).expect("history entry must serialise");
+    let _ = append(&line);
+}
+
+/// Add `line` to the history file, trimming it when it has grown too long.
+fn append(line: &str) -> std::io::Result<()> {
+    let path = path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let lines: Vec<&str> = existing.lines().collect();
+    if lines.len() + 1 >= MAX_ENTRIES {
+        let mut kept = lines[lines.len() + 1 - KEEP..].join("\n");
+        kept.push('\n');
+        kept.push_str(line);
+        kept.push('\n');
+        return fs::write(&path, kept);
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{line}")
+}
+
+/// The latest call to each number, newest first, at most `limit` of them.
+pub fn recent(limit: usize) -> Vec<Entry> {
+    let raw = fs::read_to_string(path()).unwrap_or_default();
+    let mut entries: Vec<Entry> = Vec::new();
+    for entry in raw.lines().rev().filter_map(|l| serde_json::from_str::<Entry>(l).ok()) {
+        if entries.len() == limit {
+            break;
+        }
+        if !entries.iter().any(|e| e.number == entry.number) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
@@ -0,0 +1,95 @@
+//! Single-key input while `dial` is attached to a call.
+//!
+//! [`Keypad`] switches the terminal out of line mode (no Enter needed, keys
+//! not echoed) and delivers each key press as a `char`.  Signals still work,
+//! so Ctrl-C reaches the handler in [`crate::session::terminated`].  The
+//! previous terminal mode is restored when the keypad is dropped.
+
+use std::io::{IsTerminal, Read};
+
+use tokio::sync::mpsc;
+
+pub struct Keypad {
+    keys: mpsc::UnboundedReceiver<char>,
+    _mode: KeyMode,
+}
+
+impl Keypad {
+    /// `None` when stdin is not an interactive terminal (piped, redirected,
+    /// or a platform without terminal mode support).
+    pub fn open() -> Option<Self> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        let mode = KeyMode::enable()?;
+
+        let (tx, keys) = mpsc::unbounded_channel();
+        // Plain thread: a pending read must not hold up runtime shutdown
+        std::thread::spawn(move || {
+            let mut stdin = std::io::stdin();
+            let mut byte = [0u8; 1];
+            while let Ok(1) = stdin.read(&mut byte) {
+                // Escape sequences and multi-byte keys are of no use here
+                if byte[0].is_ascii() && tx.send(byte[0] as char).is_err() {
+                    break;
+                }
+            }
+        });
+        Some(Self { keys, _mode: mode })
+    }
+
+    /// The next key pressed; `None` once stdin is closed.
+    pub async fn next(&mut self) -> Option<char> {
+        self.keys.recv().await
+    }
+}
+
+// ── Terminal mode ─────────────────────────────────────────────────────────────
+
+/// Canonical mode and echo turned off for as long as this lives.
+#[cfg(unix)]
+struct KeyMode {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl KeyMode {
+    fn enable() -> Option<Self> {
+        // SAFETY: plain termios calls on fd 0 with a properly sized struct
+        unsafe {
+            let mut term: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut term) != 0 {
+                return None;
+            }
+            let original = term;
+            term.c_lflag &= !(libc::ICANON | libc::ECHO);
+            term.c_cc[libc::VMIN] = 1;
+            term.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &term) != 0 {
+                return None;
+            }
+            Some(Self { original })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for KeyMode {
+    fn drop(&mut self) {
+        // SAFETY: restores the attributes read in `enable`
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct KeyMode;
+
+#[cfg(not(unix))]
+impl KeyMode {
+    fn enable() -> Option<Self> {
+        // Windows consoles need their own API; keep to line mode there
+        None
+    }
+}
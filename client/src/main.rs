@@ -1,19 +1,46 @@
 mod api;
 mod bluetooth;
+mod commands;
 mod config;
+mod contactcsv;
+mod contacts;
+mod dialstring;
 mod discover;
 mod errors;
+mod events;
+mod extract;
+mod gateway;
+mod history;
+mod keypad;
+mod phone;
+mod retry;
+mod session;
+mod sms;
+mod tls;
+mod vcard;
 
 use std::time::Duration;
 
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 
-use api::{GatewayClient, validate_phone};
-use bluetooth::{activate_hfp, list_bt_cards, mac_to_card_name, switch_to_a2dp, switch_to_hfp};
+use bluetooth::{list_bt_cards, mac_to_card_name, switch_to_a2dp, switch_to_hfp};
+use commands::answer::AnswerArgs;
+use commands::call::CallArgs;
+use commands::conference::ConferenceArgs;
+use commands::contacts::ContactsCmd;
+use commands::control::ControlArgs;
+use commands::dtmf::DtmfArgs;
+use commands::extract::ExtractArgs;
+use commands::hangup::HangupArgs;
+use commands::reject::RejectArgs;
+use commands::sms::SmsArgs;
+use commands::ussd::UssdArgs;
+use commands::watch::WatchArgs;
 use config::Config;
 use discover::discover_gateway;
 use errors::DialError;
+use gateway::Gateway;
 
 // ── CLI definition ─────────────────────────────────────────────────────────────
 
@@ -21,30 +48,64 @@ use errors::DialError;
 #[derive(Parser)]
 #[command(name = "dial", version, about, long_about = None)]
 struct Cli {
+    #[command(flatten)]
+    globals: GlobalArgs,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Options accepted by every subcommand.
+#[derive(clap::Args)]
+struct GlobalArgs {
     /// Override discovery timeout in seconds (default: 5)
     #[arg(long, global = true, default_value = "5")]
     timeout: u64,
 
-    #[command(subcommand)]
-    command: Commands,
+    /// When the gateway answers 429 Too Many Requests, wait for its
+    /// Retry-After and try again instead of failing (useful in batch scripts)
+    #[arg(long, global = true)]
+    wait_on_rate_limit: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Initiate a phone call via a connected Android device
-    Call {
-        /// Device ID shown in the PhoneConnect app (e.g. android_fd9de1fb)
-        device_id: String,
-
-        /// Phone number in E.164 format (e.g. +919876543210)
-        number: String,
-
-        /// Bluetooth MAC of your phone (e.g. AA:BB:CC:DD:EE:FF).
-        /// When supplied, the BT card is automatically switched to HFP before
-        /// the call so audio routes to your laptop speakers/mic.
-        #[arg(long, value_name = "MAC")]
-        bt_mac: Option<String>,
-    },
+    Call(CallArgs),
+
+    /// End the active call on a device
+    Hangup(HangupArgs),
+
+    /// Answer the call ringing on a device (audio moves to the laptop when
+    /// `bt_mac` is configured)
+    Answer(AnswerArgs),
+
+    /// Decline the call ringing on a device, optionally texting the caller
+    Reject(RejectArgs),
+
+    /// Press keys (DTMF tones) in the call active on a device
+    Dtmf(DtmfArgs),
+
+    /// Add a second party to the call active on a device and merge the calls
+    Conference(ConferenceArgs),
+
+    /// Mute, toggle the speakerphone, or hold the call active on a device
+    Control(ControlArgs),
+
+    /// Send a text message via a connected Android device
+    Sms(SmsArgs),
+
+    /// Run a USSD code (balance, data packs, carrier menus) on a device.
+    ///
+    /// Android only lets apps send single USSD requests, not reply inside an
+    /// open session, so a menu choice is sent as a new chained code
+    /// (`*123#` then `2` → `*123*2#`).  Carriers whose menus only take
+    /// in-session replies can't be navigated this way; use the phone's
+    /// dialer for those.
+    Ussd(UssdArgs),
+
+    /// Print calls ringing on connected devices as they come in
+    Watch(WatchArgs),
 
     /// List devices currently connected to the gateway
     Devices,
@@ -55,6 +116,16 @@ enum Commands {
     /// Scan the LAN for a PhoneConnect gateway and save its URL to config
     Discover,
 
+    /// List the phone numbers in text read from stdin (an email signature,
+    /// a web page), normalised to E.164
+    Extract(ExtractArgs),
+
+    /// Keep names and numbers for `dial call <device> <name>`
+    Contacts {
+        #[command(subcommand)]
+        action: ContactsCmd,
+    },
+
     /// Manage configuration
     Config {
         #[command(subcommand)]
@@ -120,7 +191,7 @@ async fn main() {
         Ok(()) => {}
         Err(e) => {
             eprintln!("{} {}", "error:".red().bold(), e);
-            std::process::exit(1);
+            std::process::exit(e.exit_code());
         }
     }
 }
@@ -132,7 +203,9 @@ async fn main() {
 ///
 /// If discovery finds a gateway the new URL is **persisted** to the config file
 /// so the next invocation is instant (no re-scan unless the IP changes again).
-async fn resolve_config(timeout_secs: u64) -> Result<Config, DialError> {
+async fn resolve_config(globals: &GlobalArgs) -> Result<Config, DialError> {
+    let timeout_secs = globals.timeout;
+
     // Load or create a default config
     let mut cfg = match Config::load() {
         Ok(c) => c,
@@ -182,142 +255,126 @@ async fn resolve_config(timeout_secs: u64) -> Result<Config, DialError> {
         }
     }
 
+    // CLI flag wins over the config file
+    if globals.wait_on_rate_limit {
+        cfg.retry.get_or_insert_with(Default::default).wait_on_rate_limit = Some(true);
+    }
+
     cfg.validate()?;
     Ok(cfg)
 }
 
+/// Resolve the config and build the gateway client every online command
+/// shares, after checking the gateway speaks this binary's protocol.
+async fn connect(globals: &GlobalArgs) -> Result<(Config, Box<dyn Gateway>), DialError> {
+    let config = resolve_config(globals).await?;
+    let gateway = gateway::connect(&config)?;
+    check_protocol(gateway.as_ref()).await?;
+    Ok((config, gateway))
+}
+
+/// Make sure the gateway still serves this binary's protocol version.
+///
+/// An unreachable gateway is not an error here — the command that follows
+/// reports it with a proper message.  Versions that differ but stay
+/// compatible only earn a warning.
+async fn check_protocol(gateway: &dyn Gateway) -> Result<(), DialError> {
+    let Ok(health) = gateway.health().await else {
+        return Ok(());
+    };
+    if let Some(warning) = health.check_protocol()? {
+        eprintln!("{} {warning}", "warn:".yellow());
+    }
+    Ok(())
+}
+
 // ── Command handlers ───────────────────────────────────────────────────────────
 
 async fn run(cli: Cli) -> Result<(), DialError> {
-    let timeout_secs = cli.timeout;
+    let Cli { globals, command } = cli;
+    let timeout_secs = globals.timeout;
+
+    match command {
+        // ── dial call <device_id> <number> [--bt-mac MAC] [--follow] ──────────
+        Commands::Call(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::call::run(gateway.as_ref(), &config, args).await?;
+        }
 
-    match cli.command {
-        // ── dial call <device_id> <number> [--bt-mac MAC] ──────────────────────
-        Commands::Call { device_id, number, bt_mac } => {
-            if device_id.trim().is_empty() {
-                return Err(DialError::EmptyDeviceId);
-            }
-            validate_phone(&number)?;
-
-            // ── Resolve BT MAC: CLI flag takes precedence, then config fallback ─────
-            let config = resolve_config(timeout_secs).await?;
-            let effective_bt_mac = bt_mac.or_else(|| config.bt_mac.clone());
-            let bt_card_name = effective_bt_mac.as_deref().map(mac_to_card_name);
-
-            // ── Optional: auto-switch BT to HFP + open SCO socket ───────────
-            // `activate_hfp` switches the profile AND spawns pw-loopback
-            // processes that keep the Bluetooth SCO audio socket alive.
-            // Without this the HFP nodes stay SUSPENDED → dead silence.
-            // The session is kept alive until the call command returns.
-            let _hfp_session = if let Some(ref card) = bt_card_name {
-                use bluetooth::HfpCodec;
-                print!("{} Opening HFP call-audio channel… ", "♫".cyan());
-                match activate_hfp(card) {
-                    Ok(session) => {
-                        match &session.codec {
-                            HfpCodec::PhoneGateway =>
-                                println!("{} (Audio Gateway — phone HFP active)",
-                                    "done".green().bold()),
-                            codec =>
-                                println!("{} ({})", "done".green().bold(), codec.label()),
-                        }
-                        Some(session)
-                    }
-                    Err(e) => {
-                        eprintln!();
-                        eprintln!("{} BT HFP activation failed: {e}", "warn:".yellow());
-                        eprintln!("  Continuing — audio will stay on the phone speaker.");
-                        None
-                    }
-                }
-            } else {
-                None
-            };
+        // ── dial hangup [device_id] ───────────────────────────────────────────
+        Commands::Hangup(args) => {
+            let (config, gateway) = connect(&globals).await?;
+            commands::hangup::run(gateway.as_ref(), &config, args).await?;
+        }
 
-            let client = GatewayClient::new(&config);
+        // ── dial answer [device_id] [--bt-mac MAC] ────────────────────────────
+        Commands::Answer(args) => {
+            let (config, gateway) = connect(&globals).await?;
+            commands::answer::run(gateway.as_ref(), &config, args).await?;
+        }
 
-            println!(
-                "{} Dispatching call to {} → {}",
-                "→".cyan().bold(),
-                device_id.yellow(),
-                number.yellow()
-            );
+        // ── dial reject [device_id] [--sms TEXT] ──────────────────────────────
+        Commands::Reject(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::reject::run(gateway.as_ref(), &config, args).await?;
+        }
 
-            let result = client.call(&device_id, &number).await?;
+        // ── dial dtmf <device_id> <digits> ────────────────────────────────────
+        Commands::Dtmf(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::dtmf::run(gateway.as_ref(), &config, args).await?;
+        }
 
-            println!("{} Call command sent!", "✓".green().bold());
-            println!("  Device : {}", result.device_id.cyan());
-            println!("  Command: {}", result.command_id.dimmed());
+        // ── dial conference <device_id> <number> ──────────────────────────────
+        Commands::Conference(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::conference::run(gateway.as_ref(), &config, args).await?;
+        }
 
-            // ── Remind the user how to restore audio after the call ───────────
-            if let Some(ref _session) = _hfp_session {
-                let mac_display = effective_bt_mac.as_deref().unwrap_or("");
-                println!();
-                println!(
-                    "  {} Audio is routed to your headset via BT HFP (SCO active).",
-                    "♫".cyan()
-                );
-                println!(
-                    "  {} Keep this terminal open for the duration of the call.",
-                    "!".yellow()
-                );
-                println!(
-                    "  Press {} when you hang up to restore A2DP stereo.",
-                    "Enter".cyan()
-                );
-                // Block until Enter so the HfpSession (and its loopback
-                // processes) stays alive for the full duration of the call.
-                // Once the user presses Enter this scope exits, Drop runs,
-                // the loopbacks are killed, and A2DP is restored.
-                let _ = {
-                    let mut buf = String::new();
-                    std::io::stdin().read_line(&mut buf)
-                };
-                println!(
-                    "  {} Restoring A2DP stereo…",
-                    "♫".cyan()
-                );
-                // HfpSession::drop() fires here — kills loopbacks, restores A2DP.
-                let _ = &bt_card_name; // suppress unused-variable warning on non-Linux
-                let _ = mac_display;
-            }
+        // ── dial control <device_id> <action> ─────────────────────────────────
+        Commands::Control(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::control::run(gateway.as_ref(), &config, args).await?;
+        }
+
+        // ── dial sms <device_id> <number> [message] ───────────────────────────
+        Commands::Sms(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::sms::run(gateway.as_ref(), &config, args).await?;
+        }
+
+        // ── dial ussd <device_id> <code> ──────────────────────────────────────
+        Commands::Ussd(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::ussd::run(gateway.as_ref(), &config, args).await?;
+        }
+
+        // ── dial watch --incoming [--device ID] [--json] ──────────────────────
+        Commands::Watch(args) => {
+            args.validate()?;
+            let (config, gateway) = connect(&globals).await?;
+            commands::watch::run(gateway.as_ref(), &config, args).await?;
         }
 
         // ── dial devices ──────────────────────────────────────────────────────
         Commands::Devices => {
-            let config = resolve_config(timeout_secs).await?;
-            let client = GatewayClient::new(&config);
-            let resp   = client.devices().await?;
-
-            if resp.devices.is_empty() {
-                println!("{} No devices currently connected.", "○".dimmed());
-            } else {
-                println!("{} {} device(s) connected\n", "●".green().bold(), resp.count);
-                for dev in &resp.devices {
-                    println!(
-                        "  {} {}  (connected since {})",
-                        "─".dimmed(),
-                        dev.device_id.cyan(),
-                        dev.connected_at.dimmed()
-                    );
-                }
-            }
+            let (_, gateway) = connect(&globals).await?;
+            commands::devices::run(gateway.as_ref()).await?;
         }
 
         // ── dial status ────────────────────────────────────────────────────────
         Commands::Status => {
-            let config = resolve_config(timeout_secs).await?;
-            let client = GatewayClient::new(&config);
-            let health = client.health().await?;
-
-            println!("{} Gateway is reachable", "✓".green().bold());
-            println!("  URL:               {}", config.server_url.cyan());
-            if let Some(uptime) = health.get("uptime").and_then(|v| v.as_f64()) {
-                println!("  Uptime:            {:.0}s", uptime);
-            }
-            if let Some(count) = health.get("connectedDevices").and_then(|v| v.as_u64()) {
-                println!("  Connected devices: {}", count);
-            }
+            // Reports a protocol mismatch rather than refusing to run
+            let config = resolve_config(&globals).await?;
+            let gateway = gateway::connect(&config)?;
+            commands::status::run(gateway.as_ref(), &config).await?;
         }
 
         // ── dial discover ──────────────────────────────────────────────────────
@@ -369,6 +426,17 @@ async fn run(cli: Cli) -> Result<(), DialError> {
             }
         }
 
+        // ── dial extract ───────────────────────────────────────────────────────
+        Commands::Extract(args) => {
+            commands::extract::run(args)?;
+        }
+
+        // ── dial contacts ──────────────────────────────────────────────────────
+        Commands::Contacts { action } => {
+            action.validate()?;
+            commands::contacts::run(action)?;
+        }
+
         // ── dial config ────────────────────────────────────────────────────────
         Commands::Config { action } => match action {
             ConfigCmd::Init => {
@@ -387,10 +455,42 @@ async fn run(cli: Cli) -> Result<(), DialError> {
                 let config = Config::load()?;
                 println!("server_url = \"{}\"", config.server_url.cyan());
                 println!("token      = \"{}\"", "***".dimmed());
+                if let Some(proxy) = &config.proxy {
+                    println!("proxy      = \"{}\"", proxy.cyan());
+                }
+                if let Some(ca) = &config.ca_cert {
+                    println!("ca_cert    = \"{}\"", ca.cyan());
+                }
+                if let Some(pin) = &config.cert_sha256 {
+                    println!("cert_sha256 = \"{}\"", pin.cyan());
+                }
                 match &config.bt_mac {
                     Some(mac) if !mac.is_empty() => println!("bt_mac     = \"{}\"", mac.cyan()),
                     _ => println!("bt_mac     = {} (set to auto-switch BT on every call)", "(not set)".dimmed()),
                 }
+                println!("ack_timeout_secs = {}", config.ack_timeout().as_secs().to_string().cyan());
+                if let Some(sim) = &config.default_sim {
+                    println!("default_sim = \"{}\"", sim.cyan());
+                }
+                for (number, sim) in config.sim_by_number.iter().flatten() {
+                    println!("sim_by_number.\"{}\" = \"{}\"", number, sim.cyan());
+                }
+                if let Some(patterns) = &config.private_numbers {
+                    println!("private_numbers = {}", format!("{patterns:?}").cyan());
+                }
+                for (device, prefix) in config.caller_id_prefix.iter().flatten() {
+                    println!("caller_id_prefix.{} = \"{}\"", device, prefix.cyan());
+                }
+                if let Some(region) = &config.default_region {
+                    println!("default_region = \"{}\"", region.cyan());
+                }
+                let retry = config.retry_policy();
+                println!(
+                    "retry      = {} attempts, {} ms base delay, {} ms max delay",
+                    retry.max_attempts.to_string().cyan(),
+                    retry.base_delay.as_millis(),
+                    retry.max_delay.as_millis(),
+                );
             }
 
             ConfigCmd::SetBtMac { mac } => {
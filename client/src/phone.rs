@@ -0,0 +1,234 @@
+//! Phone numbers as people paste them, turned into E.164.
+//!
+//! `(415) 555-0100`, `098765 43210` and `+44 (0)20 7946 0018` are all fine
+//! to type.  Formatting is stripped; a number without `+` is read in the
+//! `default_region` from the config: the region's international prefix
+//! (`00`, `011`, …) becomes `+`, and its national trunk prefix (`0`, `1`, …)
+//! is replaced by the country calling code.  Without a region such numbers
+//! are rejected: they can't be put in E.164.
+
+use crate::api::validate_phone;
+use crate::errors::DialError;
+
+/// Dialling conventions of one country.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// ISO 3166-1 alpha-2 code, as written in `default_region`
+    pub code: &'static str,
+    /// Country calling code, without `+`
+    pub calling_code: &'static str,
+    /// Dialled before an international number instead of `+`
+    pub international_prefix: &'static str,
+    /// Dialled before a national number and dropped in E.164; `None` where
+    /// the leading digit is part of the number (Italy) or there is none
+    pub trunk_prefix: Option<&'static str>,
+}
+
+/// Regions `default_region` may name.  Countries with several international
+/// prefixes list the one most phones use.
+const REGIONS: &[Region] = &[
+    Region { code: "AE", calling_code: "971", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "AR", calling_code: "54",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "AT", calling_code: "43",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "AU", calling_code: "61",  international_prefix: "0011", trunk_prefix: Some("0") },
+    Region { code: "BD", calling_code: "880", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "BE", calling_code: "32",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "CA", calling_code: "1",   international_prefix: "011",  trunk_prefix: Some("1") },
+    Region { code: "CH", calling_code: "41",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "CN", calling_code: "86",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "DE", calling_code: "49",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "DK", calling_code: "45",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "EG", calling_code: "20",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "ES", calling_code: "34",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "FI", calling_code: "358", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "FR", calling_code: "33",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "GB", calling_code: "44",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "GR", calling_code: "30",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "HK", calling_code: "852", international_prefix: "001",  trunk_prefix: None },
+    Region { code: "ID", calling_code: "62",  international_prefix: "001",  trunk_prefix: Some("0") },
+    Region { code: "IE", calling_code: "353", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "IL", calling_code: "972", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "IN", calling_code: "91",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "IT", calling_code: "39",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "JP", calling_code: "81",  international_prefix: "010",  trunk_prefix: Some("0") },
+    Region { code: "KE", calling_code: "254", international_prefix: "000",  trunk_prefix: Some("0") },
+    Region { code: "KR", calling_code: "82",  international_prefix: "001",  trunk_prefix: Some("0") },
+    Region { code: "LK", calling_code: "94",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "MX", calling_code: "52",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "MY", calling_code: "60",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "NG", calling_code: "234", international_prefix: "009",  trunk_prefix: Some("0") },
+    Region { code: "NL", calling_code: "31",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "NO", calling_code: "47",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "NP", calling_code: "977", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "NZ", calling_code: "64",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "PH", calling_code: "63",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "PK", calling_code: "92",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "PL", calling_code: "48",  international_prefix: "00",   trunk_prefix: None },
+    Region { code: "PT", calling_code: "351", international_prefix: "00",   trunk_prefix: None },
+    Region { code: "RU", calling_code: "7",   international_prefix: "810",  trunk_prefix: Some("8") },
+    Region { code: "SA", calling_code: "966", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "SE", calling_code: "46",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "SG", calling_code: "65",  international_prefix: "000",  trunk_prefix: None },
+    Region { code: "TH", calling_code: "66",  international_prefix: "001",  trunk_prefix: Some("0") },
+    Region { code: "TR", calling_code: "90",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "UA", calling_code: "380", international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "US", calling_code: "1",   international_prefix: "011",  trunk_prefix: Some("1") },
+    Region { code: "VN", calling_code: "84",  international_prefix: "00",   trunk_prefix: Some("0") },
+    Region { code: "ZA", calling_code: "27",  international_prefix: "00",   trunk_prefix: Some("0") },
+];
+
+/// The region with ISO code `code` (any case).
+pub fn region(code: &str) -> Result<&'static Region, DialError> {
+    let code = code.trim();
+    REGIONS
+        .iter()
+        .find(|r| r.code.eq_ignore_ascii_case(code))
+        .ok_or_else(|| DialError::UnknownRegion {
+            region: code.to_string(),
+            known: REGIONS.iter().map(|r| r.code).collect(),
+        })
+}
+
+/// `input` in E.164 (`+` and digits), read in `region` unless it starts
+/// with `+`.  Spaces, dashes, dots, slashes and brackets are dropped, as is
+/// a bracketed trunk prefix after the country code (`+44 (0)20 …`).
+pub fn normalize(input: &str, region: Option<&Region>) -> Result<String, DialError> {
+    let number = read(input, region)?;
+    validate_phone(&number).map_err(|_| DialError::InvalidPhoneNumber(input.to_string()))?;
+    Ok(number)
+}
+
+/// The start of a number (`0800`, `+1 800`) in E.164 as [`normalize`] reads
+/// it, without the length check a whole number gets.
+pub fn normalize_prefix(input: &str, region: Option<&Region>) -> Result<String, DialError> {
+    read(input, region)
+}
+
+/// `input` as `+` and digits, read in `region` unless it starts with `+`.
+fn read(input: &str, region: Option<&Region>) -> Result<String, DialError> {
+    let (international, digits) = strip(input)?;
+    if digits.is_empty() {
+        return Err(DialError::InvalidPhoneNumber(input.to_string()));
+    }
+
+    match region {
+        _ if international => Ok(format!("+{digits}")),
+        Some(region) => match digits.strip_prefix(region.international_prefix) {
+            Some(rest) => Ok(format!("+{rest}")),
+            None => {
+                let national = region
+                    .trunk_prefix
+                    .and_then(|trunk| digits.strip_prefix(trunk))
+                    .unwrap_or(&digits);
+                Ok(format!("+{}{national}", region.calling_code))
+            }
+        },
+        None => Err(DialError::NoCountryCode(input.trim().to_string())),
+    }
+}
+
+/// Check `input` as far as that's possible before the region is known: a
+/// number with `+` must be valid E.164, any other just made of digits and
+/// separators.  [`normalize`] does the rest.
+pub fn check(input: &str) -> Result<(), DialError> {
+    let (international, digits) = strip(input)?;
+    if international {
+        validate_phone(&format!("+{digits}")).map_err(|_| DialError::InvalidPhoneNumber(input.to_string()))
+    } else if digits.is_empty() {
+        Err(DialError::InvalidPhoneNumber(input.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether `input` starts with `+`, and its digits without formatting.
+fn strip(input: &str) -> Result<(bool, String), DialError> {
+    let trimmed = input.trim();
+    let international = trimmed.starts_with('+');
+    // Written for callers at home; never dialled from abroad
+    let trimmed = if international { trimmed.replacen("(0)", "", 1) } else { trimmed.to_string() };
+
+    let mut digits = String::with_capacity(trimmed.len());
+    for c in trimmed.chars().skip(usize::from(international)) {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' | '\u{a0}' | '-' | '.' | '/' | '(' | ')' => {}
+            _ => return Err(DialError::InvalidPhoneNumber(input.to_string())),
+        }
+    }
+    Ok((international, digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_in(code: &str, input: &str) -> Result<String, DialError> {
+        normalize(input, Some(region(code).unwrap()))
+    }
+
+    #[test]
+    fn formatting_is_dropped() {
+        assert_eq!(normalize(" +1 (415) 555-0100 ", None).unwrap(), "+14155550100");
+        assert_eq!(normalize("+1.415.555.0100", None).unwrap(), "+14155550100");
+        assert_eq!(normalize("+44\u{a0}20/7946\u{a0}0018", None).unwrap(), "+442079460018");
+        assert_eq!(read_in("US", "(415) 555-0100").unwrap(), "+14155550100");
+    }
+
+    #[test]
+    fn trunk_prefixes_are_replaced_by_the_calling_code() {
+        assert_eq!(read_in("US", "1 415 555 0100").unwrap(), "+14155550100");
+        assert_eq!(read_in("GB", "020 7946 0018").unwrap(), "+442079460018");
+        assert_eq!(read_in("IN", "098765 43210").unwrap(), "+919876543210");
+        assert_eq!(read_in("RU", "8 495 123-45-67").unwrap(), "+74951234567");
+        assert_eq!(read_in("au", "(02) 9374 4000").unwrap(), "+61293744000");
+    }
+
+    #[test]
+    fn leading_digits_stay_where_there_is_no_trunk_prefix() {
+        assert_eq!(read_in("IT", "06 6982 1234").unwrap(), "+390669821234");
+        assert_eq!(read_in("DK", "32 12 34 56").unwrap(), "+4532123456");
+    }
+
+    #[test]
+    fn international_prefixes_become_plus() {
+        assert_eq!(read_in("US", "011 44 20 7946 0018").unwrap(), "+442079460018");
+        assert_eq!(read_in("GB", "00 1 415 555 0100").unwrap(), "+14155550100");
+        assert_eq!(read_in("AU", "0011 44 20 7946 0018").unwrap(), "+442079460018");
+        assert_eq!(read_in("RU", "810 44 20 7946 0018").unwrap(), "+442079460018");
+    }
+
+    #[test]
+    fn bracketed_trunk_prefix_after_the_country_code_is_dropped() {
+        assert_eq!(normalize("+44 (0)20 7946 0018", None).unwrap(), "+442079460018");
+        assert_eq!(read_in("US", "+44 (0)20 7946 0018").unwrap(), "+442079460018");
+    }
+
+    #[test]
+    fn numbers_without_plus_need_a_region() {
+        let err = normalize("(415) 555-0100", None).unwrap_err();
+        assert!(matches!(err, DialError::NoCountryCode(ref n) if n == "(415) 555-0100"), "{err}");
+        assert!(check("(415) 555-0100").is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for input in ["", "  ", "+", "+1 415 555 CALL", "415#555", "+1 415", "+1234567890123456"] {
+            let err = normalize(input, None).unwrap_err();
+            assert!(matches!(err, DialError::InvalidPhoneNumber(_)), "{input:?}: {err}");
+            assert!(check(input).is_err(), "{input:?}");
+        }
+        // Too short or long once read in the region
+        for input in ["555 01", "1 415 555 0100 0100 01"] {
+            let err = read_in("US", input).unwrap_err();
+            assert!(matches!(err, DialError::InvalidPhoneNumber(_)), "{input:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn regions_are_found_by_code_in_any_case() {
+        assert_eq!(region(" gb ").unwrap().calling_code, "44");
+        let err = region("XX").unwrap_err();
+        assert!(matches!(err, DialError::UnknownRegion { ref region, .. } if region == "XX"), "{err}");
+    }
+}
@@ -0,0 +1,367 @@
+use std::future::Future;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use colored::Colorize;
+use rand::Rng;
+
+use crate::api::{
+    AnswerOutcome, CallSpec, CommandOptions, CommandReceipt, ControlAction, ControlOutcome,
+    DevicesResponse, DtmfOutcome, HangupOutcome, HealthResponse, MergeOutcome, RejectOutcome,
+};
+use crate::errors::DialError;
+use crate::events::EventStream;
+use crate::gateway::Gateway;
+
+/// Back-off used when a 429 carries no usable `Retry-After` information.
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+
+/// Consecutive 429s tolerated before giving up, even when waiting is enabled.
+const MAX_RATE_LIMIT_WAITS: u32 = 5;
+
+// ── Policy ────────────────────────────────────────────────────────────────────
+
+/// Exponential backoff with full jitter for transient gateway errors.
+///
+/// Attempt `n` (0-based) sleeps a random duration in
+/// `[0, min(max_delay, base_delay · 2ⁿ)]` before the next try, which keeps a
+/// room full of `dial` scripts from hammering a recovering gateway in lockstep.
+///
+/// Only errors for which [`DialError::is_transient`] holds are retried.
+/// Callers must make the operation safe to repeat (e.g. an idempotency key).
+///
+/// With `wait_on_rate_limit`, a [`DialError::RateLimited`] answer sleeps for
+/// the gateway's `Retry-After` and tries again without using up an attempt.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total tries including the first one; 1 disables retrying.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub wait_on_rate_limit: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            wait_on_rate_limit: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Jittered delay to sleep after failed attempt number `attempt` (0-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        ceiling.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+
+    /// Run `op` until it succeeds, fails permanently, or attempts run out.
+    ///
+    /// Each retry is announced on stderr so the user knows why `dial` is
+    /// taking longer than usual.
+    pub async fn run<T, F, Fut>(&self, op: F) -> Result<T, DialError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, DialError>>,
+    {
+        self.drive(true, op).await
+    }
+
+    /// Run `op` once, repeating it only after 429s when `wait_on_rate_limit`
+    /// is set.  The gateway turns a rate-limited request away before acting
+    /// on it, so this is safe for any request.
+    pub async fn wait_out_rate_limits<T, F, Fut>(&self, op: F) -> Result<T, DialError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, DialError>>,
+    {
+        self.drive(false, op).await
+    }
+
+    async fn drive<T, F, Fut>(&self, retry_transient: bool, mut op: F) -> Result<T, DialError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, DialError>>,
+    {
+        let attempts = if retry_transient { self.max_attempts.max(1) } else { 1 };
+        let mut attempt = 0;
+        let mut rate_limit_waits = 0;
+        loop {
+            match op().await {
+                Err(DialError::RateLimited { retry_after }) if self.wait_on_rate_limit => {
+                    if rate_limit_waits == MAX_RATE_LIMIT_WAITS {
+                        eprintln!(
+                            "{} Still rate limited after {MAX_RATE_LIMIT_WAITS} waits — giving up",
+                            "warn:".yellow(),
+                        );
+                        return Err(DialError::RateLimited { retry_after });
+                    }
+                    let delay = retry_after.unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+                    rate_limit_waits += 1;
+                    eprintln!(
+                        "{} Rate limited by the gateway — waiting {}s before retrying",
+                        "warn:".yellow(),
+                        delay.as_secs().max(1),
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) if e.is_transient() && attempt + 1 < attempts => {
+                    let delay = self.backoff(attempt);
+                    attempt += 1;
+                    eprintln!(
+                        "{} Attempt {attempt}/{attempts} failed ({e}) — retrying in {:.1}s",
+                        "warn:".yellow(),
+                        delay.as_secs_f64(),
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+// ── Retrying gateway ──────────────────────────────────────────────────────────
+
+/// [`Gateway`] wrapper that applies a [`RetryPolicy`] to another gateway.
+///
+/// Transient errors are only retried for commands that carry an idempotency
+/// key — otherwise a retry after a lost response could ring the number (or
+/// send the text) twice.  Everything else is tried once so that `dial status`
+/// reports an unreachable gateway immediately; 429s are waited out for every
+/// request when `wait_on_rate_limit` is set.
+pub struct Retrying<G> {
+    inner: G,
+    policy: RetryPolicy,
+}
+
+impl<G: Gateway> Retrying<G> {
+    pub fn new(inner: G, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    /// Send a command under the policy: retried when `opts` carries an
+    /// idempotency key, otherwise only repeated after rate limits.
+    async fn command<T, F, Fut>(&self, opts: &CommandOptions, op: F) -> Result<T, DialError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, DialError>>,
+    {
+        if opts.idempotency_key.is_some() {
+            self.policy.run(op).await
+        } else {
+            self.policy.wait_out_rate_limits(op).await
+        }
+    }
+}
+
+#[async_trait]
+impl<G: Gateway> Gateway for Retrying<G> {
+    async fn call(
+        &self,
+        device_id: &str,
+        call: &CallSpec,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError> {
+        self.command(opts, || self.inner.call(device_id, call, opts)).await
+    }
+
+    async fn hangup(
+        &self,
+        device_id: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<HangupOutcome>, DialError> {
+        self.command(opts, || self.inner.hangup(device_id, opts)).await
+    }
+
+    async fn answer(
+        &self,
+        device_id: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<AnswerOutcome>, DialError> {
+        self.command(opts, || self.inner.answer(device_id, opts)).await
+    }
+
+    async fn reject(
+        &self,
+        device_id: &str,
+        sms: Option<&str>,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<RejectOutcome>, DialError> {
+        self.command(opts, || self.inner.reject(device_id, sms, opts)).await
+    }
+
+    async fn sms(
+        &self,
+        device_id: &str,
+        number: &str,
+        text: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError> {
+        self.command(opts, || self.inner.sms(device_id, number, text, opts)).await
+    }
+
+    async fn dtmf(
+        &self,
+        device_id: &str,
+        digits: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<DtmfOutcome>, DialError> {
+        self.command(opts, || self.inner.dtmf(device_id, digits, opts)).await
+    }
+
+    async fn merge(
+        &self,
+        device_id: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<MergeOutcome>, DialError> {
+        self.command(opts, || self.inner.merge(device_id, opts)).await
+    }
+
+    async fn control(
+        &self,
+        device_id: &str,
+        action: ControlAction,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt<ControlOutcome>, DialError> {
+        self.command(opts, || self.inner.control(device_id, action, opts)).await
+    }
+
+    async fn ussd(
+        &self,
+        device_id: &str,
+        code: &str,
+        opts: &CommandOptions,
+    ) -> Result<CommandReceipt, DialError> {
+        self.command(opts, || self.inner.ussd(device_id, code, opts)).await
+    }
+
+    async fn devices(&self) -> Result<DevicesResponse, DialError> {
+        self.policy.wait_out_rate_limits(|| self.inner.devices()).await
+    }
+
+    async fn health(&self) -> Result<HealthResponse, DialError> {
+        self.policy.wait_out_rate_limits(|| self.inner.health()).await
+    }
+
+    async fn events(&self, device_id: Option<&str>) -> Result<EventStream, DialError> {
+        self.policy.wait_out_rate_limits(|| self.inner.events(device_id)).await
+    }
+
+    fn endpoint(&self) -> &str {
+        self.inner.endpoint()
+    }
+
+    fn transport(&self) -> String {
+        self.inner.transport()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway::fake::FakeGateway;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+            wait_on_rate_limit: false,
+        }
+    }
+
+    fn unavailable() -> DialError {
+        DialError::GatewayError { status: 503, body: "Service Unavailable".into() }
+    }
+
+    fn rate_limited() -> DialError {
+        DialError::RateLimited { retry_after: Some(Duration::from_millis(1)) }
+    }
+
+    fn keyed() -> CommandOptions {
+        CommandOptions { ack_timeout: None, idempotency_key: Some("key-1".into()) }
+    }
+
+    #[test]
+    fn backoff_never_exceeds_its_ceiling() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            ..policy()
+        };
+        for attempt in 0..40 {
+            let ceiling = Duration::from_millis(100 << attempt.min(3)).min(policy.max_delay);
+            assert!(policy.backoff(attempt) <= ceiling, "attempt {attempt}");
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let gateway = Retrying::new(FakeGateway::failing([unavailable(), unavailable()]), policy());
+        let receipt = gateway.hangup("android_1", &keyed()).await.unwrap();
+        assert_eq!(receipt.device_id, "android_1");
+        assert_eq!(gateway.inner.requests(), ["hangup"; 3]);
+    }
+
+    #[tokio::test]
+    async fn gives_up_once_attempts_run_out() {
+        let gateway = Retrying::new(FakeGateway::failing((0..5).map(|_| unavailable())), policy());
+        let err = gateway.dtmf("android_1", "1", &keyed()).await.err().unwrap();
+        assert!(matches!(err, DialError::GatewayError { status: 503, .. }));
+        assert_eq!(gateway.inner.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_not_retried() {
+        let gateway = Retrying::new(FakeGateway::failing([DialError::Unauthorized]), policy());
+        let err = gateway.merge("android_1", &keyed()).await.err().unwrap();
+        assert!(matches!(err, DialError::Unauthorized));
+        assert_eq!(gateway.inner.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn commands_without_a_key_are_not_retried() {
+        let gateway = Retrying::new(FakeGateway::failing([unavailable()]), policy());
+        let err = gateway.hangup("android_1", &CommandOptions::default()).await.err().unwrap();
+        assert!(matches!(err, DialError::GatewayError { status: 503, .. }));
+        assert_eq!(gateway.inner.requests(), ["hangup"]);
+    }
+
+    #[tokio::test]
+    async fn lookups_are_not_retried() {
+        let gateway = Retrying::new(FakeGateway::failing([unavailable()]), policy());
+        assert!(gateway.devices().await.is_err());
+        assert_eq!(gateway.inner.requests(), ["devices"]);
+    }
+
+    #[tokio::test]
+    async fn rate_limits_are_waited_out_for_every_request() {
+        let waiting = RetryPolicy { wait_on_rate_limit: true, ..policy() };
+        let gateway = Retrying::new(FakeGateway::failing((0..4).map(|_| rate_limited())), waiting);
+        gateway.devices().await.unwrap();
+        // Waits don't use up the attempts a transient error gets
+        assert_eq!(gateway.inner.requests().len(), 5);
+
+        let gateway = Retrying::new(FakeGateway::failing([rate_limited()]), policy());
+        let err = gateway.health().await.err().unwrap();
+        assert!(matches!(err, DialError::RateLimited { .. }));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_waits_run_out() {
+        let waiting = RetryPolicy { wait_on_rate_limit: true, ..policy() };
+        let errors = (0..=MAX_RATE_LIMIT_WAITS).map(|_| rate_limited());
+        let gateway = Retrying::new(FakeGateway::failing(errors), waiting);
+        let err = gateway.hangup("android_1", &keyed()).await.err().unwrap();
+        assert!(matches!(err, DialError::RateLimited { .. }));
+        assert_eq!(gateway.inner.requests().len() as u32, MAX_RATE_LIMIT_WAITS + 1);
+    }
+}
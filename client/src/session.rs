@@ -0,0 +1,268 @@
+//! Registry of live HFP call-audio sessions.
+//!
+//! `dial call --bt-mac …` keeps an [`HfpSession`](crate::bluetooth::HfpSession)
+//! alive for as long as the call lasts, but the call is often ended from a
+//! *different* invocation (`dial hangup`).  While it owns a session, the
+//! `dial call` process records its PID in
+//! `$XDG_RUNTIME_DIR/phoneconnect/hfp-<device>.pid` and holds an exclusive
+//! `flock` on that file; `dial hangup` only trusts a PID whose file is still
+//! locked — the lock dies with its owner, so a stale file can never point
+//! SIGTERM at an unrelated process that reused the PID.  [`terminated`]
+//! turns the signal into an orderly return so `HfpSession::drop` can kill the
+//! loopbacks and restore A2DP.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// ── Registration (owner side) ─────────────────────────────────────────────────
+
+/// Proof that this process owns the HFP session for a device.
+/// The PID file is removed again on drop, which also releases its lock.
+pub struct SessionLock {
+    path: PathBuf,
+    _file: File,
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Record this process as the owner of `device_id`'s HFP session.
+///
+/// Best-effort: if the runtime directory is not writable, or another live
+/// `dial call` already owns the session, the session still works, it just
+/// can't be closed by `dial hangup`.
+pub fn register(device_id: &str) -> Option<SessionLock> {
+    claim(pid_file(device_id))
+}
+
+/// [`register`] with the PID file at `path`.
+fn claim(path: PathBuf) -> Option<SessionLock> {
+    fs::create_dir_all(path.parent()?).ok()?;
+    // Another `register` or `release` may unlink the file between our open
+    // and our lock; the lock only counts if it is on the file at `path`
+    for _ in 0..3 {
+        let mut file = OpenOptions::new().create(true).truncate(false).write(true).open(&path).ok()?;
+        if !try_lock(&file) {
+            return None;
+        }
+        if !is_same_file(&file, &path) {
+            continue;
+        }
+        // The PID is only written under the lock, so two owners can never
+        // both pass the check above
+        file.set_len(0).ok()?;
+        file.write_all(std::process::id().to_string().as_bytes()).ok()?;
+        return Some(SessionLock { path, _file: file });
+    }
+    None
+}
+
+/// Why [`terminated`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// SIGTERM, as sent by `dial hangup`
+    HungUp,
+    /// Ctrl-C
+    Interrupted,
+}
+
+/// Resolves when this process is asked to stop (SIGTERM from `dial hangup`,
+/// or Ctrl-C), so the caller can return and let its `HfpSession` drop.
+pub async fn terminated() -> Stop {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut term) = signal(SignalKind::terminate()) {
+            return tokio::select! {
+                _ = term.recv() => Stop::HungUp,
+                _ = tokio::signal::ctrl_c() => Stop::Interrupted,
+            };
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+    Stop::Interrupted
+}
+
+// ── Release (other side) ──────────────────────────────────────────────────────
+
+/// Ask the process that owns `device_id`'s HFP session to close it.
+///
+/// Returns the owner's PID if one was signalled, `None` if no live session
+/// was registered.
+pub fn release(device_id: &str) -> Option<u32> {
+    signal_owner(&pid_file(device_id))
+}
+
+/// [`release`] with the PID file at `path`.
+fn signal_owner(path: &Path) -> Option<u32> {
+    let file = File::open(path).ok()?;
+    if !is_locked(&file) {
+        // Stale file left behind by a crashed `dial call`
+        let _ = fs::remove_file(path);
+        return None;
+    }
+    // Empty while its owner is still writing it
+    let pid: u32 = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    if pid == std::process::id() {
+        return None;
+    }
+    signal_terminate(pid).then_some(pid)
+}
+
+/// Whether a live process holds the lock on `file`.
+fn is_locked(file: &File) -> bool {
+    // Taking the lock ourselves means nobody else holds it; it is released
+    // again when `file` is closed
+    !try_lock(file)
+}
+
+#[cfg(unix)]
+fn try_lock(file: &File) -> bool {
+    use std::os::fd::AsRawFd;
+    // SAFETY: flock on a descriptor `file` keeps open for the call
+    unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) == 0 }
+}
+
+/// Whether `file` is still the file at `path`, not one unlinked since.
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(open), Ok(named)) => (open.dev(), open.ino()) == (named.dev(), named.ino()),
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn signal_terminate(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else { return false };
+    // SAFETY: kill has no memory-safety preconditions
+    unsafe { libc::kill(pid, libc::SIGTERM) == 0 }
+}
+
+#[cfg(not(unix))]
+fn try_lock(_file: &File) -> bool {
+    true
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_file: &File, _path: &Path) -> bool {
+    true
+}
+
+#[cfg(not(unix))]
+fn signal_terminate(_pid: u32) -> bool {
+    // Windows / macOS route call audio without an HfpSession
+    false
+}
+
+// ── Paths ─────────────────────────────────────────────────────────────────────
+
+fn pid_file(device_id: &str) -> PathBuf {
+    let dir = dirs::runtime_dir().unwrap_or_else(std::env::temp_dir).join("phoneconnect");
+    pid_file_in(&dir, device_id)
+}
+
+fn pid_file_in(dir: &Path, device_id: &str) -> PathBuf {
+    // Device IDs come from the user; keep them from escaping the directory
+    let safe: String = device_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    dir.join(format!("hfp-{safe}.pid"))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A PID file in a fresh directory of its own, removed on drop.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("phoneconnect-{name}-{}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn pid_file(&self) -> PathBuf {
+            pid_file_in(&self.0, "phone/../1")
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn device_ids_stay_inside_the_directory() {
+        let scratch = Scratch::new("paths");
+        assert_eq!(scratch.pid_file(), scratch.0.join("hfp-phone____1.pid"));
+    }
+
+    #[test]
+    fn stale_pid_files_are_never_signalled() {
+        let scratch = Scratch::new("stale");
+        let mut bystander = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let path = scratch.pid_file();
+        fs::write(&path, bystander.id().to_string()).unwrap();
+
+        assert_eq!(signal_owner(&path), None);
+        assert!(!path.exists());
+        assert!(bystander.try_wait().unwrap().is_none(), "bystander was signalled");
+        bystander.kill().unwrap();
+        bystander.wait().unwrap();
+    }
+
+    #[test]
+    fn a_live_session_is_locked_until_dropped() {
+        let scratch = Scratch::new("live");
+        let path = scratch.pid_file();
+        let lock = claim(path.clone()).expect("session registers");
+        assert!(claim(path.clone()).is_none(), "second owner registered");
+        assert_eq!(fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+        // Our own PID is never signalled
+        assert_eq!(signal_owner(&path), None);
+        assert!(path.exists());
+
+        drop(lock);
+        assert!(!path.exists());
+        assert!(claim(path).is_some());
+    }
+
+    #[test]
+    fn a_stale_file_is_taken_over() {
+        let scratch = Scratch::new("takeover");
+        let path = scratch.pid_file();
+        fs::write(&path, "4294967295\n").unwrap();
+        let _lock = claim(path.clone()).expect("session registers");
+        assert_eq!(fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+    }
+
+    #[test]
+    fn racing_owners_register_once() {
+        let scratch = Scratch::new("race");
+        let path = scratch.pid_file();
+        let barrier = std::sync::Barrier::new(8);
+        let owners = std::thread::scope(|s| {
+            let racers: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        barrier.wait();
+                        claim(path.clone())
+                    })
+                })
+                .collect();
+            // Every lock is still held once all racers are done
+            racers.into_iter().map(|r| r.join().unwrap()).collect::<Vec<_>>()
+        });
+        assert_eq!(owners.iter().flatten().count(), 1);
+    }
+}
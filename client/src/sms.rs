@@ -0,0 +1,111 @@
+//! Length and segment accounting for outgoing SMS.
+//!
+//! Android picks the encoding the same way: GSM-7 (7-bit, 160 characters per
+//! message) when every character is in the GSM 03.38 alphabet, otherwise
+//! UCS-2 (16-bit, 70 per message).  Longer texts are split into concatenated
+//! segments that each lose a few characters to the reassembly header.
+
+use std::fmt;
+
+/// GSM 03.38 basic character set — one septet each.
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+                          ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+
+/// GSM 03.38 extension table — two septets each (escape + character).
+const GSM7_EXTENDED: &str = "^{}\\[~]|€\u{000C}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Gsm7,
+    Ucs2,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Gsm7 => write!(f, "GSM-7"),
+            Encoding::Ucs2 => write!(f, "UCS-2"),
+        }
+    }
+}
+
+/// How a text will be sent.
+#[derive(Debug, Clone)]
+pub struct SmsLayout {
+    pub encoding: Encoding,
+    /// Characters as the user sees them
+    pub chars: usize,
+    /// Number of SMS the carrier will bill
+    pub segments: usize,
+    /// Distinct characters that forced UCS-2, in order of appearance
+    pub non_gsm: Vec<char>,
+}
+
+/// Work out encoding, length and segment count for `text`.
+pub fn analyze(text: &str) -> SmsLayout {
+    let mut septets = 0;
+    let mut non_gsm: Vec<char> = Vec::new();
+    for c in text.chars() {
+        if GSM7_BASIC.contains(c) {
+            septets += 1;
+        } else if GSM7_EXTENDED.contains(c) {
+            septets += 2;
+        } else if !non_gsm.contains(&c) {
+            non_gsm.push(c);
+        }
+    }
+
+    let (encoding, units, single, multi) = if non_gsm.is_empty() {
+        (Encoding::Gsm7, septets, 160, 153)
+    } else {
+        (Encoding::Ucs2, text.encode_utf16().count(), 70, 67)
+    };
+    // Septets for GSM-7, UTF-16 code units for UCS-2
+    let segments = match units {
+        0 => 1,
+        n if n <= single => 1,
+        n => n.div_ceil(multi),
+    };
+
+    SmsLayout { encoding, chars: text.chars().count(), segments, non_gsm }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gsm7_fits_160_characters_in_one_message() {
+        let one = analyze(&"a".repeat(160));
+        assert_eq!((one.encoding, one.chars, one.segments), (Encoding::Gsm7, 160, 1));
+        assert_eq!(analyze(&"a".repeat(161)).segments, 2);
+        assert_eq!(analyze(&"a".repeat(306)).segments, 2);
+        assert_eq!(analyze(&"a".repeat(307)).segments, 3);
+    }
+
+    #[test]
+    fn ucs2_fits_70_characters_in_one_message() {
+        let one = analyze(&"я".repeat(70));
+        assert_eq!((one.encoding, one.chars, one.segments), (Encoding::Ucs2, 70, 1));
+        assert_eq!(one.non_gsm, ['я']);
+        assert_eq!(analyze(&"я".repeat(71)).segments, 2);
+        assert_eq!(analyze(&"я".repeat(134)).segments, 2);
+        assert_eq!(analyze(&"я".repeat(135)).segments, 3);
+    }
+
+    #[test]
+    fn extension_characters_take_two_septets() {
+        for c in ['€', '^'] {
+            let text = format!("{}{c}", "a".repeat(158));
+            let layout = analyze(&text);
+            assert_eq!((layout.encoding, layout.chars, layout.segments), (Encoding::Gsm7, 159, 1));
+            assert_eq!(analyze(&format!("a{text}")).segments, 2, "{c}");
+        }
+    }
+
+    #[test]
+    fn empty_text_is_one_message() {
+        let layout = analyze("");
+        assert_eq!((layout.encoding, layout.chars, layout.segments), (Encoding::Gsm7, 0, 1));
+    }
+}
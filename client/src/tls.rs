@@ -0,0 +1,218 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::ring;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, OtherError, RootCertStore, SignatureScheme};
+use sha2::{Digest, Sha256};
+
+use crate::errors::DialError;
+
+// ── Client configuration ──────────────────────────────────────────────────────
+
+/// Build the rustls configuration for an `https://` gateway.
+///
+/// Trust anchors are the bundled Mozilla roots plus every certificate in
+/// `ca_cert` (a PEM file — typically the gateway's self-signed certificate or
+/// the office CA that issued it).  A certificate listed in `ca_cert` is also
+/// accepted as-is when the gateway presents it directly, since self-signed
+/// certificates are usually marked as CAs and would otherwise be rejected.
+///
+/// With `pin` set, the gateway's leaf certificate must hash to exactly that
+/// SHA-256 fingerprint.  A matching pin is trusted on its own, like an SSH
+/// host key, so a pinned self-signed certificate needs no `ca_cert`; a
+/// mismatch is always fatal, whatever the CA chain says.
+pub fn client_config(ca_cert: Option<&Path>, pin: Option<&str>) -> Result<ClientConfig, DialError> {
+    let provider = Arc::new(ring::default_provider());
+    let pin = pin.map(parse_fingerprint).transpose()?;
+
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let extra = match ca_cert {
+        Some(path) => load_pem_certs(path)?,
+        None => Vec::new(),
+    };
+    for cert in &extra {
+        roots.add(cert.clone()).map_err(|e| {
+            DialError::TlsConfig(format!("unusable certificate in ca_cert: {e}"))
+        })?;
+    }
+
+    let webpki = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| DialError::TlsConfig(e.to_string()))?;
+
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| DialError::TlsConfig(e.to_string()))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(GatewayVerifier { webpki, extra, pin }))
+        .with_no_client_auth();
+    Ok(config)
+}
+
+/// Every certificate in a PEM file.  A file without any is an error — it is
+/// almost certainly the wrong path (or a private key).
+fn load_pem_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, DialError> {
+    let file = File::open(path).map_err(|e| {
+        DialError::TlsConfig(format!("cannot read ca_cert {}: {e}", path.display()))
+    })?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| DialError::TlsConfig(format!("{}: {e}", path.display())))?;
+    if certs.is_empty() {
+        return Err(DialError::TlsConfig(format!(
+            "{} contains no PEM certificates",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+// ── Fingerprints ──────────────────────────────────────────────────────────────
+
+/// Parse a SHA-256 fingerprint as printed by
+/// `openssl x509 -noout -fingerprint -sha256` (`AB:CD:…`) or as bare hex,
+/// optionally prefixed with `sha256:`.
+pub fn parse_fingerprint(s: &str) -> Result<[u8; 32], DialError> {
+    let s = s.trim();
+    let hex_str: String = s
+        .strip_prefix("sha256:")
+        .or_else(|| s.strip_prefix("SHA256:"))
+        .unwrap_or(s)
+        .chars()
+        .filter(|c| *c != ':')
+        .collect();
+
+    let invalid = || {
+        DialError::TlsConfig(format!(
+            "cert_sha256 '{s}' is not a SHA-256 fingerprint (expected 64 hex digits)"
+        ))
+    };
+    if hex_str.len() != 64 || !hex_str.is_ascii() {
+        return Err(invalid());
+    }
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex_str[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(out)
+}
+
+/// `AB:CD:…` — the same form `openssl` prints, so the two can be compared by eye.
+pub fn format_fingerprint(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X}")).collect::<Vec<_>>().join(":")
+}
+
+// ── Verifier ──────────────────────────────────────────────────────────────────
+
+/// Certificate check for the gateway connection: pin first, then certificates
+/// trusted verbatim via `ca_cert`, then ordinary WebPKI chain validation.
+/// Handshake signatures are always verified, so the server must hold the
+/// private key of whatever certificate it presents.
+#[derive(Debug)]
+struct GatewayVerifier {
+    webpki: Arc<WebPkiServerVerifier>,
+    extra: Vec<CertificateDer<'static>>,
+    pin: Option<[u8; 32]>,
+}
+
+impl ServerCertVerifier for GatewayVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(pin) = self.pin {
+            let actual: [u8; 32] = Sha256::digest(end_entity.as_ref()).into();
+            if actual == pin {
+                return Ok(ServerCertVerified::assertion());
+            }
+            return Err(rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(
+                Arc::new(PinMismatch {
+                    expected: format_fingerprint(&pin),
+                    actual: format_fingerprint(&actual),
+                }),
+            ))));
+        }
+        if self.extra.iter().any(|c| c.as_ref() == end_entity.as_ref()) {
+            return Ok(ServerCertVerified::assertion());
+        }
+        self.webpki
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.webpki.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.webpki.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.webpki.supported_verify_schemes()
+    }
+}
+
+/// Raised inside the handshake when the pin doesn't match; recovered from the
+/// reqwest error chain by [`classify`].
+#[derive(Debug)]
+struct PinMismatch {
+    expected: String,
+    actual: String,
+}
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "certificate fingerprint {} does not match pin {}", self.actual, self.expected)
+    }
+}
+
+impl StdError for PinMismatch {}
+
+// ── Error classification ──────────────────────────────────────────────────────
+
+/// Turn a TLS failure buried in a reqwest error chain into a [`DialError`]
+/// that tells the user what to fix.  `None` for non-TLS errors.
+pub fn classify(err: &reqwest::Error) -> Option<DialError> {
+    let mut next: Option<&(dyn StdError + 'static)> = err.source();
+    while let Some(e) = next {
+        if let Some(tls) = e.downcast_ref::<rustls::Error>() {
+            if let rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(other))) = tls {
+                if let Some(m) = other.downcast_ref::<PinMismatch>() {
+                    return Some(DialError::CertificatePinMismatch {
+                        expected: m.expected.clone(),
+                        actual: m.actual.clone(),
+                    });
+                }
+            }
+            return Some(DialError::Tls(tls.to_string()));
+        }
+        // io::Error::source() skips the error it wraps, so step into it by hand
+        next = match e.downcast_ref::<std::io::Error>().and_then(|io| io.get_ref()) {
+            Some(inner) => Some(inner as &(dyn StdError + 'static)),
+            None => e.source(),
+        };
+    }
+    None
+}
@@ -0,0 +1,445 @@
+//! vCard 3.0 / 4.0 (RFC 2426, RFC 6350), as far as a phone book needs it.
+//!
+//! Reading takes `FN` (else `N`, else `ORG`) as the name, every `TEL` with
+//! its `TYPE` parameters as a number, and `ORG` / `NOTE` as notes.  Apple
+//! and Google write custom labels as a grouped `item1.X-ABLabel` next to
+//! `item1.TEL`; those are read, and written back the same way.  vCard 2.1
+//! bare types (`TEL;CELL:…`) are accepted too.
+
+use std::collections::HashMap;
+
+use crate::contacts::{normalize_label, Contact, Imported};
+
+// ── Reading ───────────────────────────────────────────────────────────────────
+
+/// Every `BEGIN:VCARD` … `END:VCARD` block in `text`.
+pub fn parse(text: &str) -> Vec<Imported> {
+    let mut cards = Vec::new();
+    let mut card: Option<Card> = None;
+
+    for line in unfold(text) {
+        let Some(prop) = Property::parse(&line) else { continue };
+        match prop.name.as_str() {
+            "BEGIN" if prop.value.eq_ignore_ascii_case("VCARD") => {
+                card = Some(Card { source: format!("card {}", cards.len() + 1), ..Card::default() });
+            }
+            "END" if prop.value.eq_ignore_ascii_case("VCARD") => {
+                if let Some(done) = card.take() {
+                    cards.push(done.finish());
+                }
+            }
+            _ => {
+                if let Some(card) = &mut card {
+                    card.add(prop);
+                }
+            }
+        }
+    }
+    cards
+}
+
+/// Properties of one card collected so far.
+#[derive(Default)]
+struct Card {
+    source: String,
+    formatted_name: Option<String>,
+    structured_name: Option<String>,
+    org: Option<String>,
+    note: Option<String>,
+    /// `(group, types, value)`
+    tels: Vec<(Option<String>, Vec<String>, String)>,
+    /// `X-ABLabel` values by group
+    labels: HashMap<String, String>,
+}
+
+impl Card {
+    fn add(&mut self, prop: Property) {
+        match prop.name.as_str() {
+            "FN" => self.formatted_name = non_empty(unescape(&prop.value)),
+            "N" => {
+                // Family;Given;Additional;Prefix;Suffix
+                let parts = split_escaped(&prop.value);
+                let part = |i: usize| parts.get(i).map(String::as_str).unwrap_or_default();
+                let name = [part(1), part(2), part(0)]
+                    .iter()
+                    .filter(|p| !p.is_empty())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                self.structured_name = non_empty(name);
+            }
+            "ORG" => self.org = split_escaped(&prop.value).into_iter().next().and_then(non_empty),
+            "NOTE" => self.note = non_empty(unescape(&prop.value)),
+            "TEL" => {
+                let value = prop.value.trim();
+                // vCard 4.0 writes numbers as tel: URIs, parameters after ';';
+                // an extension becomes keys to press once connected
+                let value = match value.strip_prefix("tel:") {
+                    Some(uri) => {
+                        let mut parts = uri.split(';');
+                        let number = parts.next().unwrap_or_default();
+                        let extension = parts
+                            .find_map(|p| p.strip_prefix("ext="))
+                            .filter(|e| !e.is_empty() && e.chars().all(|c| c.is_ascii_digit()));
+                        match extension {
+                            Some(ext) => format!("{number},{ext}"),
+                            None => number.to_string(),
+                        }
+                    }
+                    None => value.to_string(),
+                };
+                self.tels.push((prop.group, prop.types, unescape(&value)));
+            }
+            "X-ABLABEL" => {
+                if let Some(group) = prop.group {
+                    self.labels.insert(group, unescape(&prop.value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> Imported {
+        let name = self.formatted_name.or(self.structured_name).or_else(|| self.org.clone());
+        let numbers = self
+            .tels
+            .into_iter()
+            .map(|(group, types, number)| {
+                let custom = group.and_then(|g| self.labels.get(&g)).and_then(|l| normalize_label(l));
+                let label = custom.or_else(|| types.iter().find_map(|t| normalize_label(t)));
+                (label, number)
+            })
+            .collect();
+        // The company is worth keeping unless it already is the name
+        let org = self.org.filter(|org| name.as_deref() != Some(org.as_str()));
+        let notes: Vec<String> = org.into_iter().chain(self.note).collect();
+        Imported {
+            source: self.source,
+            name,
+            numbers,
+            notes: non_empty(notes.join(" · ")),
+        }
+    }
+}
+
+/// One content line: `[group.]NAME[;PARAM=VALUE…]:value`.
+struct Property {
+    group: Option<String>,
+    /// Upper-cased
+    name: String,
+    /// `TYPE` parameter values (and 2.1 bare parameters), upper-cased
+    types: Vec<String>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Option<Self> {
+        let colon = find_unquoted(line, ':')?;
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+        let mut params = split_unquoted(head, ';').into_iter();
+        let full_name = params.next()?.trim();
+        let (group, name) = match full_name.rsplit_once('.') {
+            Some((group, name)) => (Some(group.to_ascii_lowercase()), name),
+            None => (None, full_name),
+        };
+
+        let mut types = Vec::new();
+        for param in params {
+            let (key, values) = match param.split_once('=') {
+                Some((key, values)) => (key.trim(), values),
+                None => ("TYPE", param),
+            };
+            if key.eq_ignore_ascii_case("TYPE") {
+                types.extend(
+                    split_unquoted(values, ',')
+                        .iter()
+                        .flat_map(|v| v.trim_matches('"').split(','))
+                        .map(|v| v.trim().to_ascii_uppercase()),
+                );
+            }
+        }
+        Some(Self { group, name: name.to_ascii_uppercase(), types, value: value.to_string() })
+    }
+}
+
+/// Logical lines: continuation lines (starting with a space or tab) are
+/// joined onto the line before, as RFC 6350 §3.2 folds them.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if raw.trim().is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn find_unquoted(s: &str, needle: char) -> Option<usize> {
+    let mut quoted = false;
+    s.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        c if c == needle && !quoted => Some(i),
+        _ => None,
+    })
+}
+
+fn split_unquoted(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = s;
+    while let Some(at) = find_unquoted(rest, separator) {
+        parts.push(&rest[..at]);
+        rest = &rest[at + separator.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+/// A structured value's `;`-separated components, unescaped.
+fn split_escaped(value: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    push_unescaped(parts.last_mut().expect("never empty"), next);
+                }
+            }
+            ';' => parts.push(String::new()),
+            c => parts.last_mut().expect("never empty").push(c),
+        }
+    }
+    parts
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    push_unescaped(&mut out, next);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.trim().to_string()
+}
+
+fn push_unescaped(out: &mut String, escaped: char) {
+    match escaped {
+        'n' | 'N' => out.push('\n'),
+        c => out.push(c),
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    let s = s.trim();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+// ── Writing ───────────────────────────────────────────────────────────────────
+
+/// `contacts` as vCard 3.0, the version every address book imports.
+pub fn write(contacts: &[Contact]) -> String {
+    let mut out = String::new();
+    for contact in contacts {
+        let mut line = |l: String| fold_into(&mut out, &l);
+        line("BEGIN:VCARD".into());
+        line("VERSION:3.0".into());
+        line(format!("FN:{}", escape(&contact.name)));
+        // N is mandatory in 3.0; the last word is as good a family name as any
+        let (given, family) = contact.name.trim().rsplit_once(' ').unwrap_or(("", contact.name.trim()));
+        line(format!("N:{};{};;;", escape(family), escape(given)));
+
+        let mut group = 0;
+        for number in &contact.numbers {
+            match number.label.as_deref() {
+                None => line(format!("TEL:{}", number.number)),
+                Some(label) => match vcard_type(label) {
+                    Some(t) => line(format!("TEL;TYPE={t}:{}", number.number)),
+                    None => {
+                        group += 1;
+                        line(format!("item{group}.TEL:{}", number.number));
+                        line(format!("item{group}.X-ABLabel:{}", escape(label)));
+                    }
+                },
+            }
+        }
+        if let Some(notes) = &contact.notes {
+            line(format!("NOTE:{}", escape(notes)));
+        }
+        line("END:VCARD".into());
+    }
+    out
+}
+
+/// The `TYPE` for labels vCard has one for; custom labels get `X-ABLabel`.
+fn vcard_type(label: &str) -> Option<&'static str> {
+    match label {
+        "mobile" => Some("CELL"),
+        "work" => Some("WORK"),
+        "home" => Some("HOME"),
+        "fax" => Some("FAX"),
+        "pager" => Some("PAGER"),
+        _ => None,
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            ',' | ';' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append `line` with CRLF, folded so no physical line exceeds 75 octets.
+fn fold_into(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contacts::ContactNumber;
+
+    fn number(label: Option<&str>, number: &str) -> ContactNumber {
+        ContactNumber { label: label.map(str::to_string), number: number.to_string() }
+    }
+
+    fn labelled(numbers: &[(Option<String>, String)]) -> Vec<(Option<&str>, &str)> {
+        numbers.iter().map(|(l, n)| (l.as_deref(), n.as_str())).collect()
+    }
+
+    #[test]
+    fn written_contacts_read_back_the_same() {
+        let contacts = [
+            Contact {
+                name: "Alice Smith".to_string(),
+                notes: Some("Front desk; ask for Bob, not Carol\nWeekdays only".to_string()),
+                numbers: vec![
+                    number(Some("mobile"), "+14155550100"),
+                    number(Some("assistant"), "+14155550101"),
+                    number(None, "+14155550102"),
+                    number(Some("work"), "+14155550103,12"),
+                ],
+            },
+            Contact { name: "Zoë".to_string(), notes: None, numbers: vec![number(Some("work"), "+442079460018")] },
+        ];
+        let read = parse(&write(&contacts));
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].name.as_deref(), Some("Alice Smith"));
+        assert_eq!(read[0].notes, contacts[0].notes);
+        assert_eq!(
+            labelled(&read[0].numbers),
+            [
+                (Some("mobile"), "+14155550100"),
+                (Some("assistant"), "+14155550101"),
+                (None, "+14155550102"),
+                (Some("work"), "+14155550103,12"),
+            ]
+        );
+        assert_eq!(read[1].name.as_deref(), Some("Zoë"));
+        assert_eq!(labelled(&read[1].numbers), [(Some("work"), "+442079460018")]);
+        assert_eq!(read[1].source, "card 2");
+    }
+
+    #[test]
+    fn long_lines_are_folded_and_unfolded() {
+        let name = "Maximiliane Österreicher-Großglockner von und zu Hohenschwangau-Füssen";
+        let contact = Contact { name: name.to_string(), notes: Some("é".repeat(60)), numbers: Vec::new() };
+        let text = write(&[contact]);
+
+        assert!(text.split("\r\n").all(|line| line.len() <= 75), "{text}");
+        assert!(text.contains("\r\n "));
+        let read = parse(&text);
+        assert_eq!(read[0].name.as_deref(), Some(name));
+        assert_eq!(read[0].notes, Some("é".repeat(60)));
+    }
+
+    #[test]
+    fn reads_other_writers() {
+        let text = "\
+BEGIN:VCARD\r
+VERSION:4.0\r
+N:Doe;Jane;Q.;Dr.;\r
+ORG:ACME\\, Inc.;Sales\r
+TEL;VALUE=uri;TYPE=\"cell,voice\":tel:+1-415-555-0100;ext=12\r
+item1.TEL:+1 415 555 0101\r
+item1.X-ABLabel:_$!<Mobile>!$_\r
+item2.TEL:+1 415 555 0102\r
+ITEM2.X-ABLABEL:Gate\r
+END:VCARD\r
+BEGIN:VCARD\r
+VERSION:2.1\r
+ORG:Pizza Place\r
+TEL;WORK;VOICE:020 7946 0018\r
+END:VCARD\r
+";
+        let read = parse(text);
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].name.as_deref(), Some("Jane Q. Doe"));
+        assert_eq!(read[0].notes.as_deref(), Some("ACME, Inc."));
+        assert_eq!(
+            labelled(&read[0].numbers),
+            [(Some("mobile"), "+1-415-555-0100,12"), (Some("mobile"), "+1 415 555 0101"), (Some("gate"), "+1 415 555 0102")]
+        );
+        // The company stands in for a missing name, and isn't repeated as a note
+        assert_eq!(read[1].name.as_deref(), Some("Pizza Place"));
+        assert_eq!(read[1].notes, None);
+        assert_eq!(labelled(&read[1].numbers), [(Some("work"), "020 7946 0018")]);
+    }
+
+    #[test]
+    fn skips_what_it_cannot_read() {
+        let text = "\
+TEL:+14155550199
+BEGIN:VCARD
+this line has no colon
+TEL;TYPE=HOME:+14155550100
+NOTE:
+END:VCARD
+garbage between cards
+BEGIN:VCARD
+FN:Never Finished
+TEL:+14155550101
+";
+        let read = parse(text);
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].name, None);
+        assert_eq!(read[0].notes, None);
+        assert_eq!(labelled(&read[0].numbers), [(Some("home"), "+14155550100")]);
+
+        assert!(parse("").is_empty());
+        assert!(parse("not a vcard at all").is_empty());
+    }
+}
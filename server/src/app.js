@@ -1,5 +1,7 @@
 import "dotenv/config";
+import fs from "fs";
 import http from "http";
+import https from "https";
 import express from "express";
 import { WebSocketServer } from "ws";
 import { v4 as uuidv4 } from "uuid";
@@ -7,6 +9,7 @@ import logger from "./logger.js";
 import connectionManager from "./connectionManager.js";
 import { handleMessage } from "./messageHandler.js";
 import apiRouter from "./routes/call.js";
+import eventsRouter from "./routes/events.js";
 import { startMdnsAdvertisement, stopMdnsAdvertisement } from "./discovery.js";
 
 const log = logger.child({ module: "app" });
@@ -18,11 +21,41 @@ app.use(express.json());
 
 // Attach all REST routes
 app.use("/", apiRouter);
+app.use("/", eventsRouter);
 
-// ── HTTP server ───────────────────────────────────────────────────────────────
+// ── HTTP(S) server ────────────────────────────────────────────────────────────
 
 const PORT = parseInt(process.env.PORT || "3000", 10);
-const server = http.createServer(app);
+
+/**
+ * Serve HTTPS (and therefore WSS) when both TLS_CERT_FILE and TLS_KEY_FILE
+ * are set, so bearer tokens never cross the network in cleartext.
+ */
+const TLS_CERT_FILE = process.env.TLS_CERT_FILE;
+const TLS_KEY_FILE = process.env.TLS_KEY_FILE;
+const useTls = Boolean(TLS_CERT_FILE && TLS_KEY_FILE);
+
+if (Boolean(TLS_CERT_FILE) !== Boolean(TLS_KEY_FILE)) {
+  log.error("TLS_CERT_FILE and TLS_KEY_FILE must be set together");
+  process.exit(1);
+}
+
+const server = useTls
+  ? https.createServer(
+      {
+        cert: fs.readFileSync(TLS_CERT_FILE),
+        key: fs.readFileSync(TLS_KEY_FILE),
+      },
+      app
+    )
+  : http.createServer(app);
+
+/**
+ * Optional second listener on a Unix-domain socket (UNIX_SOCKET) for CLIs on
+ * the gateway host itself: REST + SSE only — devices always connect over TCP.
+ */
+const UNIX_SOCKET = process.env.UNIX_SOCKET;
+const unixServer = UNIX_SOCKET ? http.createServer(app) : null;
 
 // ── WebSocket server ──────────────────────────────────────────────────────────
 
@@ -69,6 +102,7 @@ wss.on("error", (err) => {
 function shutdown(signal) {
   log.info({ signal }, "Shutting down gracefully…");
   stopMdnsAdvertisement();
+  unixServer?.close();
   wss.close(() => {
     server.close(() => {
       log.info("Server closed");
@@ -92,12 +126,27 @@ server.listen(PORT, () => {
   log.info(
     {
       port: PORT,
+      tls: useTls,
       wsPath: "/ws",
       env: process.env.NODE_ENV || "production",
     },
     "PhoneConnect gateway started"
   );
   // Advertise on the LAN so Android devices auto-discover this server
-  startMdnsAdvertisement(PORT);
+  startMdnsAdvertisement(PORT, { tls: useTls });
 });
 
+if (unixServer) {
+  // A socket file left behind by a crash would make listen() fail with EADDRINUSE
+  fs.rmSync(UNIX_SOCKET, { force: true });
+  unixServer.listen(UNIX_SOCKET, () => {
+    // Owner + group only: the bearer token is still required, but there is
+    // no reason to let every local user knock
+    fs.chmodSync(UNIX_SOCKET, 0o660);
+    log.info({ socket: UNIX_SOCKET }, "Listening on Unix socket");
+  });
+  unixServer.on("error", (err) => {
+    log.error({ socket: UNIX_SOCKET, err: err.message }, "Unix socket listener failed");
+  });
+}
+
@@ -18,12 +18,21 @@ const PONG_TIMEOUT_MS = 10_000;
  *   'connected'    (deviceId)
  *   'disconnected' (deviceId, reason)
  *   'message'      (deviceId, parsedObject)
+ *   'status'       (deviceId, { state, number, commandId })
+ *   'smsStatus'    (deviceId, { state, commandId, reason })
+ *   'ussdResponse' (deviceId, { commandId, ok, message })
+ *   'legStatus'    (deviceId, { number, state, conference, commandId })
+ *   'incoming'     (deviceId, { state, number, name })
  */
 class ConnectionManager extends EventEmitter {
   constructor() {
     super();
-    /** @type {Map<string, { ws: WebSocket, pingTimer: NodeJS.Timeout, pongTimer?: NodeJS.Timeout, connectedAt: Date }>} */
+    // Every open GET /events stream adds its own listeners
+    this.setMaxListeners(0);
+    /** @type {Map<string, { ws: WebSocket, pingTimer: NodeJS.Timeout, pongTimer?: NodeJS.Timeout, connectedAt: Date, sims: object[] | null }>} */
     this._devices = new Map();
+    /** @type {Map<string, { deviceId: string, settle: (outcome: string) => void }>} keyed by commandId */
+    this._pendingAcks = new Map();
   }
 
   // ── Registration ────────────────────────────────────────────────────────────
@@ -31,8 +40,10 @@ class ConnectionManager extends EventEmitter {
   /**
    * Register a new authenticated WebSocket for a device.
    * Replaces any existing connection for the same deviceId.
+   *
+   * `info.sims` is the SIM list the device reported in AUTH (null if none).
    */
-  register(deviceId, ws) {
+  register(deviceId, ws, info = {}) {
     // Evict existing connection if any
     if (this._devices.has(deviceId)) {
       log.info({ deviceId }, "Replacing existing connection");
@@ -42,6 +53,7 @@ class ConnectionManager extends EventEmitter {
     const entry = {
       ws,
       connectedAt: new Date(),
+      sims: info.sims ?? null,
       pingTimer: null,
       pongTimer: null,
     };
@@ -86,6 +98,47 @@ class ConnectionManager extends EventEmitter {
     }
   }
 
+  // ── Acknowledgements ────────────────────────────────────────────────────────
+
+  /**
+   * Wait for the device to ACK `commandId`.
+   *
+   * Resolves (never rejects) with `{ outcome, result }`, `outcome` being one of:
+   *   "acked"        — the device sent ACK { id: commandId, result? }
+   *   "timeout"      — no ACK within `timeoutMs`
+   *   "disconnected" — the device's socket closed first
+   * `result` is the ACK's optional `result` object (e.g. END_CALL reports
+   * whether a call was active), otherwise null.
+   */
+  awaitAck(deviceId, commandId, timeoutMs) {
+    return new Promise((resolve) => {
+      const timer = setTimeout(() => settle("timeout"), timeoutMs);
+      const onDisconnected = (id) => {
+        if (id === deviceId) settle("disconnected");
+      };
+      const settle = (outcome, result = null) => {
+        clearTimeout(timer);
+        this.off("disconnected", onDisconnected);
+        this._pendingAcks.delete(commandId);
+        resolve({ outcome, result });
+      };
+
+      this.on("disconnected", onDisconnected);
+      this._pendingAcks.set(commandId, { deviceId, settle });
+    });
+  }
+
+  /**
+   * Settle a pending {@link awaitAck} for `commandId`.
+   * Returns false if nobody was waiting (or the ACK came from another device).
+   */
+  resolveAck(deviceId, commandId, result = null) {
+    const pending = this._pendingAcks.get(commandId);
+    if (!pending || pending.deviceId !== deviceId) return false;
+    pending.settle("acked", result);
+    return true;
+  }
+
   // ── Queries ──────────────────────────────────────────────────────────────────
 
   isConnected(deviceId) {
@@ -99,7 +152,11 @@ class ConnectionManager extends EventEmitter {
   stats() {
     const devices = [];
     for (const [id, entry] of this._devices) {
-      devices.push({ deviceId: id, connectedAt: entry.connectedAt });
+      devices.push({
+        deviceId: id,
+        connectedAt: entry.connectedAt,
+        ...(entry.sims && { sims: entry.sims }),
+      });
     }
     return { count: devices.length, devices };
   }
@@ -18,9 +18,13 @@ let service = null;
  * Any Android device on the same network running the PhoneConnect app
  * will discover this automatically via NsdManager and auto-fill the IP.
  *
+ * The TXT `protocol` field is "wss" when the server speaks TLS, so clients
+ * build `https://` / `wss://` URLs instead of plaintext ones.
+ *
  * @param {number} port  The HTTP/WS port the server is listening on.
+ * @param {{ tls?: boolean }} [opts]
  */
-export function startMdnsAdvertisement(port) {
+export function startMdnsAdvertisement(port, { tls = false } = {}) {
   try {
     bonjour = new Bonjour();
 
@@ -31,14 +35,14 @@ export function startMdnsAdvertisement(port) {
       // TXT record: apps can read these via NsdServiceInfo.attributes
       txt: {
         version: "1",
-        protocol: "ws",
+        protocol: tls ? "wss" : "ws",
         path: "/ws",
       },
     });
 
     service.on("up", () => {
       log.info(
-        { port, serviceType: `_${SERVICE_TYPE}._tcp.local` },
+        { port, tls, serviceType: `_${SERVICE_TYPE}._tcp.local` },
         "mDNS service advertised — Android devices on the same network will auto-discover"
       );
     });
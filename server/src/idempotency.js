@@ -0,0 +1,47 @@
+import logger from "./logger.js";
+
+const log = logger.child({ module: "idempotency" });
+
+/** How long a dispatched command's outcome is remembered (ms). */
+const TTL_MS = 10 * 60_000;
+
+/**
+ * Outcomes of commands dispatched with an `Idempotency-Key` header.
+ *
+ * Each entry holds a promise for `{ status, body }` so a retry that arrives
+ * while the original request is still waiting for an ACK shares its result
+ * instead of sending the command to the device a second time.
+ *
+ * @type {Map<string, { fingerprint: string, outcome: Promise<{ status: number, body: object }>, expiresAt: number }>}
+ */
+const entries = new Map();
+
+function sweep(now) {
+  for (const [key, entry] of entries) {
+    if (entry.expiresAt <= now) entries.delete(key);
+  }
+}
+
+/**
+ * Look up a previous dispatch for `key`.
+ *
+ * Returns `null` when unseen, `{ conflict: true }` when the key was used for a
+ * different request, or `{ outcome }` to replay.
+ */
+export function lookup(key, fingerprint) {
+  sweep(Date.now());
+  const entry = entries.get(key);
+  if (!entry) return null;
+  if (entry.fingerprint !== fingerprint) return { conflict: true };
+  log.info({ key }, "Replaying idempotent request");
+  return { outcome: entry.outcome };
+}
+
+/**
+ * Record the (possibly still pending) outcome of a dispatched command.
+ * Only call this once the command has actually been sent to a device —
+ * rejections such as 404 must stay retryable.
+ */
+export function remember(key, fingerprint, outcome) {
+  entries.set(key, { fingerprint, outcome, expiresAt: Date.now() + TTL_MS });
+}
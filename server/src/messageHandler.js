@@ -10,9 +10,12 @@ const log = logger.child({ module: "messageHandler" });
  *
  * Expected inbound message types (device → gateway):
  *
- *   AUTH     { type, deviceId, token }          — first message after connect
- *   STATUS   { type, state, number? }           — call lifecycle update
- *   ACK      { type, id }                       — command acknowledgement
+ *   AUTH     { type, deviceId, token, sims? }   — first message after connect
+ *   STATUS   { type, state, number?, id? }      — call lifecycle update
+ *   SMS_STATUS { type, id, state, reason? }     — SMS sent / delivered / failed
+ *   USSD_RESPONSE { type, id, ok, message }     — carrier reply to a USSD request
+ *   LEG_STATUS { type, number, state, conference?, id? } — one call of several (conferences)
+ *   ACK      { type, id, result? }              — command acknowledgement
  *   PONG     { type }                           — response to JSON PING
  *
  * @param {WebSocket} ws          - The raw socket that sent the message
@@ -46,6 +49,14 @@ export function handleMessage(ws, rawMessage, state) {
   switch (type) {
     case "STATUS":
       return handleStatus(msg, state);
+    case "SMS_STATUS":
+      return handleSmsStatus(msg, state);
+    case "USSD_RESPONSE":
+      return handleUssdResponse(msg, state);
+    case "LEG_STATUS":
+      return handleLegStatus(msg, state);
+    case "INCOMING":
+      return handleIncoming(msg, state);
     case "ACK":
       return handleAck(msg, state);
     case "PONG":
@@ -79,13 +90,26 @@ function handleAuth(ws, msg, state) {
   state.authenticated = true;
   state.deviceId = deviceId.trim();
 
-  connectionManager.register(state.deviceId, ws);
+  connectionManager.register(state.deviceId, ws, { sims: parseSims(msg.sims) });
   ws.send(JSON.stringify({ type: "AUTH_OK", deviceId: state.deviceId }));
   log.info({ deviceId: state.deviceId }, "AUTH OK");
 }
 
+/**
+ * Keep the well-formed entries of the SIM list a device reports in AUTH:
+ *   [{ slot: 1, label?: "Work", carrier?: "Vodafone" }, ...]
+ * Returns null when the device didn't report any (older app versions).
+ */
+function parseSims(sims) {
+  if (!Array.isArray(sims)) return null;
+  const text = (v) => (typeof v === "string" && v.trim() !== "" ? v.trim().slice(0, 64) : undefined);
+  return sims
+    .filter((sim) => Number.isInteger(sim?.slot) && sim.slot > 0)
+    .map((sim) => ({ slot: sim.slot, label: text(sim.label), carrier: text(sim.carrier) }));
+}
+
 function handleStatus(msg, state) {
-  const VALID_STATES = ["CALL_STARTED", "CALL_ENDED", "CALL_FAILED"];
+  const VALID_STATES = ["CALL_STARTED", "CALL_ANSWERED", "CALL_ENDED", "CALL_FAILED"];
   const callState = (msg.state || "").toUpperCase();
 
   if (!VALID_STATES.includes(callState)) {
@@ -98,24 +122,199 @@ function handleStatus(msg, state) {
     "Call status update"
   );
 
-  // Future: emit to REST status SSE stream or a webhook here
+  // Fan out to GET /events subscribers (e.g. `dial call --follow`)
+  connectionManager.emit("status", state.deviceId, {
+    state: callState,
+    number: msg.number ?? null,
+    commandId: msg.id || null,
+  });
+}
+
+function handleSmsStatus(msg, state) {
+  const VALID_STATES = ["SMS_SENT", "SMS_DELIVERED", "SMS_FAILED"];
+  const smsState = (msg.state || "").toUpperCase();
+
+  if (!VALID_STATES.includes(smsState)) {
+    log.warn({ deviceId: state.deviceId, smsState }, "Unknown SMS state");
+    return;
+  }
+
+  log.info({ deviceId: state.deviceId, smsState, commandId: msg.id || null }, "SMS status update");
+
+  connectionManager.emit("smsStatus", state.deviceId, {
+    state: smsState,
+    commandId: msg.id || null,
+    reason: msg.reason || null,
+  });
+}
+
+function handleLegStatus(msg, state) {
+  const VALID_STATES = ["DIALING", "RINGING", "ACTIVE", "HOLDING", "DISCONNECTED"];
+  const legState = (msg.state || "").toUpperCase();
+
+  if (!VALID_STATES.includes(legState) || typeof msg.number !== "string") {
+    log.warn({ deviceId: state.deviceId, legState }, "Malformed leg status");
+    return;
+  }
+
+  log.info({ deviceId: state.deviceId, legState, commandId: msg.id || null }, "Leg status update");
+
+  connectionManager.emit("legStatus", state.deviceId, {
+    number: msg.number,
+    state: legState,
+    conference: msg.conference === true,
+    commandId: msg.id || null,
+  });
+}
+
+function handleIncoming(msg, state) {
+  const VALID_STATES = ["RINGING", "ANSWERED", "MISSED"];
+  const callState = (msg.state || "").toUpperCase();
+
+  if (!VALID_STATES.includes(callState)) {
+    log.warn({ deviceId: state.deviceId, callState }, "Unknown incoming call state");
+    return;
+  }
+  const text = (v) => (typeof v === "string" && v.trim() !== "" ? v.trim().slice(0, 128) : null);
+
+  log.info({ deviceId: state.deviceId, callState }, "Incoming call");
+
+  // Fan out to GET /events subscribers (`dial watch --incoming`)
+  connectionManager.emit("incoming", state.deviceId, {
+    state: callState,
+    number: text(msg.number),
+    name: text(msg.name),
+  });
+}
+
+function handleUssdResponse(msg, state) {
+  const ok = msg.ok === true;
+  const message = typeof msg.message === "string" ? msg.message : "";
+
+  log.info({ deviceId: state.deviceId, ok, commandId: msg.id || null }, "USSD response");
+
+  connectionManager.emit("ussdResponse", state.deviceId, {
+    commandId: msg.id || null,
+    ok,
+    message,
+  });
 }
 
 function handleAck(msg, state) {
   const id = msg.id || "";
-  log.debug({ deviceId: state.deviceId, commandId: id }, "ACK received");
-  // Future: resolve a pending-command promise here for guaranteed delivery
+  const result = msg.result && typeof msg.result === "object" ? msg.result : null;
+  const awaited = connectionManager.resolveAck(state.deviceId, id, result);
+  log.debug({ deviceId: state.deviceId, commandId: id, awaited }, "ACK received");
 }
 
 // ── Outbound helpers (gateway → device) ───────────────────────────────────────
 
 /**
  * Build a CALL command payload with a unique ID for dedup on the device.
+ * Options are only included when set, so older apps see the usual payload.
  */
-export function buildCallCommand(number) {
+export function buildCallCommand(number, { sim, withholdCallerId = false, callerIdPrefix } = {}) {
   return {
     type: "CALL",
     number,
+    ...(sim !== undefined && { sim }),
+    ...(withholdCallerId && { withholdCallerId: true }),
+    ...(withholdCallerId && callerIdPrefix !== undefined && { callerIdPrefix }),
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build an END_CALL command.  The device ACKs it only after trying to hang
+ * up, with `result: { wasActive, ended }`.
+ */
+export function buildEndCallCommand() {
+  return {
+    type: "END_CALL",
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build an ANSWER_CALL command: pick up the call ringing on the device.
+ * The device ACKs with `result: { ringing, answered, number?, reason? }`.
+ */
+export function buildAnswerCommand() {
+  return {
+    type: "ANSWER_CALL",
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build a REJECT_CALL command: decline the ringing call and, with `sms`,
+ * text the caller.  The device ACKs with `result: { ringing, rejected,
+ * number?, reason? }`; the text's progress follows as SMS_STATUS.
+ */
+export function buildRejectCommand(sms) {
+  return {
+    type: "REJECT_CALL",
+    ...(sms !== undefined && { sms }),
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build an SMS command.  The device ACKs on receipt and later reports
+ * SMS_STATUS (sent, then delivered if the carrier confirms) for its `id`.
+ */
+export function buildSmsCommand(number, text) {
+  return {
+    type: "SMS",
+    number,
+    text,
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build a MERGE command: join the device's calls into one conference.
+ * The device ACKs with `result: { merged, reason? }`.
+ */
+export function buildMergeCommand() {
+  return {
+    type: "MERGE",
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build an in-call CONTROL command (mute / unmute / speaker / hold / resume).
+ * The device ACKs with `result: { applied, reason?, state? }`.
+ */
+export function buildControlCommand(action) {
+  return {
+    type: "CONTROL",
+    action,
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build a DTMF command.  The device ACKs after queueing the tones, with
+ * `result: { played, reason? }`.
+ */
+export function buildDtmfCommand(digits) {
+  return {
+    type: "DTMF",
+    digits,
+    id: uuidv4(),
+  };
+}
+
+/**
+ * Build a USSD command.  The device ACKs on receipt and later reports the
+ * carrier's reply as USSD_RESPONSE for its `id`.
+ */
+export function buildUssdCommand(code) {
+  return {
+    type: "USSD",
+    code,
     id: uuidv4(),
   };
 }
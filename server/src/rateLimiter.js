@@ -9,6 +9,9 @@ const log = logger.child({ module: "rateLimiter" });
  * Defaults (overridable via env):
  *   RATE_LIMIT_WINDOW_MS  = 60_000   (1 minute window)
  *   RATE_LIMIT_MAX        = 30       (30 requests per window per IP)
+ *
+ * Rejections carry `Retry-After` and the standard `RateLimit-*` headers so
+ * callers such as `dial --wait-on-rate-limit` know how long to back off.
  */
 const windowMs = parseInt(process.env.RATE_LIMIT_WINDOW_MS || "60000", 10);
 const max = parseInt(process.env.RATE_LIMIT_MAX || "30", 10);
@@ -18,11 +21,20 @@ export const apiLimiter = rateLimit({
   max,
   standardHeaders: true,   // Return RateLimit-* headers
   legacyHeaders: false,
+  // Requests over the Unix socket have no IP; they share a single bucket
+  keyGenerator: (req) => req.ip || "unix-socket",
   handler(req, res) {
-    log.warn({ ip: req.ip }, "Rate limit exceeded");
+    // Seconds until this client's window resets (falls back to a full window)
+    const resetTime = req.rateLimit?.resetTime;
+    const retryAfter = resetTime
+      ? Math.max(1, Math.ceil((resetTime.getTime() - Date.now()) / 1000))
+      : Math.ceil(windowMs / 1000);
+
+    log.warn({ ip: req.ip, retryAfter }, "Rate limit exceeded");
+    res.set("Retry-After", String(retryAfter));
     res.status(429).json({
       error: "Too many requests",
-      retryAfter: Math.ceil(windowMs / 1000),
+      retryAfter,
     });
   },
 });
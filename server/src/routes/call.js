@@ -1,31 +1,182 @@
 import { Router } from "express";
 import { body, validationResult } from "express-validator";
 import connectionManager from "../connectionManager.js";
-import { buildCallCommand } from "../messageHandler.js";
+import {
+  buildAnswerCommand,
+  buildCallCommand,
+  buildControlCommand,
+  buildDtmfCommand,
+  buildEndCallCommand,
+  buildMergeCommand,
+  buildRejectCommand,
+  buildSmsCommand,
+  buildUssdCommand,
+} from "../messageHandler.js";
 import { requireAuth } from "../auth.js";
 import { apiLimiter } from "../rateLimiter.js";
+import { GATEWAY_VERSION, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION } from "../version.js";
+import * as idempotency from "../idempotency.js";
 import logger from "../logger.js";
 
 const log = logger.child({ module: "routes/call" });
 const router = Router();
 
+/** Upper bound for how long POST /call may hold the request open for an ACK. */
+const MAX_ACK_TIMEOUT_MS = 60_000;
+
+/** Most DTMF keys accepted in one command. */
+const MAX_DTMF_DIGITS = 64;
+
+/** Longest USSD string a network accepts (3GPP TS 23.038: 182 characters). */
+const MAX_USSD_LENGTH = 182;
+
+/** Longest SMS text accepted — 10 concatenated GSM-7 segments. */
+const MAX_SMS_LENGTH = 1530;
+
+/** Longest SIM selector accepted (a slot number or the SIM's display name). */
+const MAX_SIM_LENGTH = 64;
+
+/** In-call actions POST /control forwards to the device. */
+const CONTROL_ACTIONS = ["mute", "unmute", "speaker", "hold", "resume"];
+
+/** Carrier codes that withhold caller ID: #31# (GSM), *67 (North America), … */
+const CALLER_ID_PREFIX = /^[0-9*#]{1,8}$/;
+
 // ── Validation rules ──────────────────────────────────────────────────────────
 
+const deviceIdRule = body("deviceId")
+  .isString()
+  .trim()
+  .notEmpty()
+  .withMessage("deviceId is required");
+
+const ackTimeoutRule = body("ackTimeoutMs")
+  .optional()
+  .isInt({ min: 0, max: MAX_ACK_TIMEOUT_MS })
+  .withMessage(`ackTimeoutMs must be an integer between 0 and ${MAX_ACK_TIMEOUT_MS}`);
+
+const numberRule = body("number")
+  .isString()
+  .trim()
+  .notEmpty()
+  .matches(/^\+?[1-9]\d{6,14}$/)
+  .withMessage("number must be a valid E.164 phone number");
+
 const callValidation = [
-  body("deviceId")
+  deviceIdRule,
+  numberRule,
+
+  body("sim")
+    .optional()
     .isString()
     .trim()
     .notEmpty()
-    .withMessage("deviceId is required"),
+    .isLength({ max: MAX_SIM_LENGTH })
+    .withMessage(`sim must be a slot number or a SIM label of at most ${MAX_SIM_LENGTH} characters`),
+
+  body("withholdCallerId")
+    .optional()
+    .isBoolean({ strict: true })
+    .withMessage("withholdCallerId must be true or false"),
+
+  body("callerIdPrefix")
+    .optional()
+    .isString()
+    .matches(CALLER_ID_PREFIX)
+    .withMessage("callerIdPrefix must be 1-8 of 0-9, * and #"),
+
+  ackTimeoutRule,
+];
+
+const hangupValidation = [deviceIdRule, ackTimeoutRule];
+
+const answerValidation = [deviceIdRule, ackTimeoutRule];
+
+const rejectValidation = [
+  deviceIdRule,
+
+  body("sms")
+    .optional()
+    .isString()
+    .withMessage("sms must be a string")
+    .bail()
+    .custom((text) => text.trim().length > 0)
+    .withMessage("sms must not be empty")
+    .isLength({ max: MAX_SMS_LENGTH })
+    .withMessage(`sms must be at most ${MAX_SMS_LENGTH} characters`),
+
+  ackTimeoutRule,
+];
+
+const smsValidation = [
+  deviceIdRule,
+  numberRule,
+
+  body("text")
+    .isString()
+    .withMessage("text is required")
+    .bail()
+    .custom((text) => text.trim().length > 0)
+    .withMessage("text must not be empty")
+    .isLength({ max: MAX_SMS_LENGTH })
+    .withMessage(`text must be at most ${MAX_SMS_LENGTH} characters`),
+
+  ackTimeoutRule,
+];
+
+const dtmfValidation = [
+  deviceIdRule,
 
-  body("number")
+  body("digits")
     .isString()
     .trim()
-    .notEmpty()
-    .matches(/^\+?[1-9]\d{6,14}$/)
-    .withMessage("number must be a valid E.164 phone number"),
+    .matches(new RegExp(`^[0-9*#A-D]{1,${MAX_DTMF_DIGITS}}$`))
+    .withMessage(`digits must be 1–${MAX_DTMF_DIGITS} DTMF keys (0-9, *, #, A-D)`),
+
+  ackTimeoutRule,
+];
+
+const mergeValidation = [deviceIdRule, ackTimeoutRule];
+
+const controlValidation = [
+  deviceIdRule,
+
+  body("action")
+    .isString()
+    .trim()
+    .toLowerCase()
+    .isIn(CONTROL_ACTIONS)
+    .withMessage(`action must be one of ${CONTROL_ACTIONS.join(", ")}`),
+
+  ackTimeoutRule,
+];
+
+const ussdValidation = [
+  deviceIdRule,
+
+  body("code")
+    .isString()
+    .trim()
+    .isLength({ max: MAX_USSD_LENGTH })
+    .withMessage(`code must be at most ${MAX_USSD_LENGTH} characters`)
+    .bail()
+    .matches(/^[*#][0-9*#]*\d[0-9*#]*#$/)
+    .withMessage("code must be a USSD string such as *123# or *100*1#"),
+
+  ackTimeoutRule,
 ];
 
+/** 400 with per-field details if validation failed; returns true if it did. */
+function rejectInvalid(req, res) {
+  const errors = validationResult(req);
+  if (errors.isEmpty()) return false;
+  res.status(400).json({
+    error: "Validation failed",
+    details: errors.array().map((e) => ({ field: e.path, msg: e.msg })),
+  });
+  return true;
+}
+
 // ── POST /call ────────────────────────────────────────────────────────────────
 
 /**
@@ -34,58 +185,450 @@ const callValidation = [
  * Request:
  *   POST /call
  *   Authorization: Bearer <token>
- *   { "deviceId": "android_abc123", "number": "+919876543210" }
+ *   { "deviceId": "android_abc123", "number": "+919876543210", "sim"?: "2",
+ *     "withholdCallerId"?: true, "callerIdPrefix"?: "*67", "ackTimeoutMs"?: 5000 }
+ *
+ * `sim` picks the line on dual-SIM phones: a 1-based slot number or the SIM's
+ * label as listed by GET /devices.  Without it the phone's default is used.
+ *
+ * `withholdCallerId` asks the device to hide the caller's number.  It dials
+ * `callerIdPrefix` before the number — the device's own default (#31#) when
+ * absent, since carriers differ (e.g. *67 in North America).
+ *
+ * When `ackTimeoutMs` is given (and > 0) the response is held until the
+ * device ACKs the command, the timeout elapses, or the device drops.
+ *
+ * An optional `Idempotency-Key` header makes retries safe: once a command has
+ * been sent for a key, repeating the request replays the original response
+ * (marked `Idempotent-Replayed: true`) instead of ringing the number again.
  *
  * Responses:
- *   200  { ok: true,  commandId, deviceId }
+ *   200  { ok: true,  commandId, deviceId, delivered }
  *   400  { error: "Validation failed", details: [...] }
  *   401  { error: "Unauthorized" }
  *   404  { error: "Device not connected", deviceId }
+ *   410  { error: "Device disconnected", commandId, deviceId }
+ *   422  { error: "Idempotency-Key was already used for a different request" }
  *   429  { error: "Too many requests" }
  *   500  { error: "Internal server error" }
+ *   504  { error: "Not acknowledged", commandId, deviceId, ackTimeoutMs }
  */
 router.post(
   "/call",
   apiLimiter,
   requireAuth,
   callValidation,
-  (req, res) => {
-    // Validation errors
-    const errors = validationResult(req);
-    if (!errors.isEmpty()) {
-      return res.status(400).json({
-        error: "Validation failed",
-        details: errors.array().map((e) => ({ field: e.path, msg: e.msg })),
-      });
-    }
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId, number, sim, withholdCallerId, callerIdPrefix } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+    const withhold = withholdCallerId === true;
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `CALL|${deviceId}|${number}|${sim ?? ""}|${withhold ? callerIdPrefix ?? "withheld" : ""}`,
+      command: () => buildCallCommand(number, { sim, withholdCallerId: withhold, callerIdPrefix }),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /hangup ──────────────────────────────────────────────────────────────
+
+/**
+ * End the active (or ringing) call on a device.
+ *
+ * Request:
+ *   POST /hangup
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "ackTimeoutMs"?: 5000 }
+ *
+ * The device ACKs END_CALL after trying to hang up, so with `ackTimeoutMs`
+ * the response carries `result: { wasActive, ended }`.  Supports
+ * `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /call, with 200
+ *   { ok: true, commandId, deviceId, delivered, result? }
+ */
+router.post(
+  "/hangup",
+  apiLimiter,
+  requireAuth,
+  hangupValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `END_CALL|${deviceId}`,
+      command: buildEndCallCommand,
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /answer ──────────────────────────────────────────────────────────────
+
+/**
+ * Pick up the call ringing on a device.
+ *
+ * Request:
+ *   POST /answer
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "ackTimeoutMs"?: 5000 }
+ *
+ * The device ACKs ANSWER_CALL after trying to answer, so with `ackTimeoutMs`
+ * the response carries `result: { ringing, answered, number?, reason? }`.
+ * Supports `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /hangup.
+ */
+router.post(
+  "/answer",
+  apiLimiter,
+  requireAuth,
+  answerValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `ANSWER_CALL|${deviceId}`,
+      command: buildAnswerCommand,
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /reject ──────────────────────────────────────────────────────────────
+
+/**
+ * Decline the call ringing on a device, optionally texting the caller.
+ *
+ * Request:
+ *   POST /reject
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "sms"?: "In a meeting", "ackTimeoutMs"?: 5000 }
+ *
+ * The device ACKs REJECT_CALL with `result: { ringing, rejected, number?,
+ * reason? }`.  When `sms` is given and the caller's number is known, the text
+ * is sent to them and its progress reported as SMS_STATUS events carrying the
+ * command's id.  Supports `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /hangup.
+ */
+router.post(
+  "/reject",
+  apiLimiter,
+  requireAuth,
+  rejectValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId, sms } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `REJECT_CALL|${deviceId}|${sms ?? ""}`,
+      command: () => buildRejectCommand(sms),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /sms ─────────────────────────────────────────────────────────────────
 
-    const { deviceId, number } = req.body;
+/**
+ * Send a text message from a device.
+ *
+ * Request:
+ *   POST /sms
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "number": "+919876543210", "text": "…", "ackTimeoutMs"?: 5000 }
+ *
+ * The device ACKs on receipt; whether the message was sent / delivered is
+ * reported afterwards as SMS_STATUS events on GET /events.  Supports
+ * `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /call.
+ */
+router.post(
+  "/sms",
+  apiLimiter,
+  requireAuth,
+  smsValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId, number, text } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `SMS|${deviceId}|${number}|${text}`,
+      command: () => buildSmsCommand(number, text),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /dtmf ────────────────────────────────────────────────────────────────
+
+/**
+ * Play DTMF tones into the device's connected call (IVR menus, conference
+ * PINs).
+ *
+ * Request:
+ *   POST /dtmf
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "digits": "1234#", "ackTimeoutMs"?: 5000 }
+ *
+ * With `ackTimeoutMs` the response carries `result: { played, reason? }`;
+ * `played` is false when no call is connected.  Supports `Idempotency-Key`
+ * like POST /call.
+ *
+ * Responses: as POST /hangup.
+ */
+router.post(
+  "/dtmf",
+  apiLimiter,
+  requireAuth,
+  dtmfValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId, digits } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
 
-    if (!connectionManager.isConnected(deviceId)) {
-      log.warn({ deviceId }, "Call requested for offline device");
-      return res.status(404).json({
-        error: "Device not connected",
-        deviceId,
-        connectedDevices: connectionManager.connectedDevices(),
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `DTMF|${deviceId}|${digits}`,
+      command: () => buildDtmfCommand(digits),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /merge ───────────────────────────────────────────────────────────────
+
+/**
+ * Merge the device's calls into a conference (`dial conference`, after the
+ * second party was called with POST /call while the first call was up).
+ *
+ * Request:
+ *   POST /merge
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "ackTimeoutMs"?: 5000 }
+ *
+ * With `ackTimeoutMs` the response carries `result: { merged, reason? }`;
+ * `merged` is false when there is nothing to merge or the network refuses.
+ * Each leg's progress is reported as LEG_STATUS events on GET /events.
+ * Supports `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /hangup.
+ */
+router.post(
+  "/merge",
+  apiLimiter,
+  requireAuth,
+  mergeValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `MERGE|${deviceId}`,
+      command: () => buildMergeCommand(),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /control ─────────────────────────────────────────────────────────────
+
+/**
+ * Mute, switch the speakerphone, or hold the device's ongoing call.
+ *
+ * Request:
+ *   POST /control
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "action": "mute", "ackTimeoutMs"?: 5000 }
+ *
+ * `action` is one of mute, unmute, speaker (toggles the speakerphone), hold
+ * and resume.  With `ackTimeoutMs` the response carries
+ * `result: { applied, reason?, state?: { muted, speaker, onHold } }` — the
+ * call's state after the action.  Supports `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /hangup.
+ */
+router.post(
+  "/control",
+  apiLimiter,
+  requireAuth,
+  controlValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId, action } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `CONTROL|${deviceId}|${action}`,
+      command: () => buildControlCommand(action),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── POST /ussd ────────────────────────────────────────────────────────────────
+
+/**
+ * Send a USSD request (balance checks, data packs, carrier menus).
+ *
+ * Request:
+ *   POST /ussd
+ *   Authorization: Bearer <token>
+ *   { "deviceId": "android_abc123", "code": "*123#", "ackTimeoutMs"?: 5000 }
+ *
+ * The device ACKs on receipt; the carrier's reply follows as a USSD_RESPONSE
+ * event on GET /events.  Menus are navigated by sending the chained code
+ * (e.g. `*123*2#`).  Supports `Idempotency-Key` like POST /call.
+ *
+ * Responses: as POST /call.
+ */
+router.post(
+  "/ussd",
+  apiLimiter,
+  requireAuth,
+  ussdValidation,
+  async (req, res) => {
+    if (rejectInvalid(req, res)) return;
+
+    const { deviceId, code } = req.body;
+    const ackTimeoutMs = parseInt(req.body.ackTimeoutMs ?? "0", 10);
+
+    return sendCommand(req, res, {
+      deviceId,
+      fingerprint: `USSD|${deviceId}|${code}`,
+      command: () => buildUssdCommand(code),
+      ackTimeoutMs,
+    });
+  }
+);
+
+// ── Command helpers ───────────────────────────────────────────────────────────
+
+/**
+ * Shared tail of every command route: honour `Idempotency-Key`, make sure the
+ * device is online, dispatch, and send the outcome.
+ *
+ * `command` is a factory so nothing is built for a replayed request.
+ */
+async function sendCommand(req, res, { deviceId, fingerprint, command, ackTimeoutMs }) {
+  const idempotencyKey = req.get("Idempotency-Key");
+
+  // Replay before the connectivity check: the device may have dropped since
+  // the original request, but that command was still sent.
+  if (idempotencyKey) {
+    const previous = idempotency.lookup(idempotencyKey, fingerprint);
+    if (previous?.conflict) {
+      return res.status(422).json({
+        error: "Idempotency-Key was already used for a different request",
       });
     }
+    if (previous) {
+      const { status, body } = await previous.outcome;
+      return res.status(status).set("Idempotent-Replayed", "true").json(body);
+    }
+  }
+
+  if (!connectionManager.isConnected(deviceId)) {
+    log.warn({ deviceId, fingerprint }, "Command requested for offline device");
+    return res.status(404).json({
+      error: "Device not connected",
+      deviceId,
+      connectedDevices: connectionManager.connectedDevices(),
+    });
+  }
 
-    const command = buildCallCommand(number);
-    const sent = connectionManager.sendTo(deviceId, command);
+  const outcome = dispatchCommand(deviceId, command(), ackTimeoutMs);
+  if (!outcome) {
+    // Nothing reached the device, so a retry with the same key may try again
+    return res.status(500).json({ error: "Internal server error" });
+  }
+  if (idempotencyKey) idempotency.remember(idempotencyKey, fingerprint, outcome);
+
+  const { status, body } = await outcome;
+  return res.status(status).json(body);
+}
+
+/**
+ * Send `command` to a connected device.  Returns `null` if it could not be
+ * sent, otherwise a promise for the `{ status, body }` to return to the
+ * caller — after the ACK when `ackTimeoutMs` > 0.
+ */
+function dispatchCommand(deviceId, command, ackTimeoutMs) {
+  // Register the waiter before sending so a fast ACK cannot slip past it
+  const ack = ackTimeoutMs > 0
+    ? connectionManager.awaitAck(deviceId, command.id, ackTimeoutMs)
+    : null;
+
+  const sent = connectionManager.sendTo(deviceId, command);
+
+  if (!sent) {
+    log.error({ deviceId }, "sendTo returned false after isConnected check");
+    return null;
+  }
+
+  log.info({ deviceId, type: command.type, commandId: command.id }, "Command dispatched");
+  return settleCommand(deviceId, command, ack, ackTimeoutMs);
+}
 
-    if (!sent) {
-      log.error({ deviceId }, "sendTo returned false after isConnected check");
-      return res.status(500).json({ error: "Internal server error" });
+/** Wait for the ACK of a sent `command`, if one was asked for. */
+async function settleCommand(deviceId, command, ack, ackTimeoutMs) {
+  let result = null;
+  if (ack) {
+    const { outcome, result: ackResult } = await ack;
+    result = ackResult;
+    if (outcome === "timeout") {
+      log.warn({ deviceId, commandId: command.id, ackTimeoutMs }, "Command not acknowledged");
+      return {
+        status: 504,
+        body: { error: "Not acknowledged", commandId: command.id, deviceId, ackTimeoutMs },
+      };
     }
+    if (outcome === "disconnected") {
+      log.warn({ deviceId, commandId: command.id }, "Device dropped before ACK");
+      return {
+        status: 410,
+        body: { error: "Device disconnected", commandId: command.id, deviceId },
+      };
+    }
+  }
 
-    log.info({ deviceId, number, commandId: command.id }, "CALL command dispatched");
-    return res.status(200).json({
+  return {
+    status: 200,
+    body: {
       ok: true,
       commandId: command.id,
       deviceId,
-    });
-  }
-);
+      delivered: ack !== null,
+      ...(result && { result }),
+    },
+  };
+}
 
 // ── GET /devices ──────────────────────────────────────────────────────────────
 
@@ -102,11 +645,15 @@ router.get("/devices", requireAuth, (_req, res) => {
 // ── GET /health ───────────────────────────────────────────────────────────────
 
 /**
- * Unauthenticated health check — used by load balancers / monitoring.
+ * Unauthenticated health check — used by load balancers / monitoring, and by
+ * the CLI to check it speaks a protocol the gateway still serves.
  */
 router.get("/health", (_req, res) => {
   res.json({
     status: "ok",
+    version: GATEWAY_VERSION,
+    protocolVersion: PROTOCOL_VERSION,
+    minProtocolVersion: MIN_PROTOCOL_VERSION,
     uptime: process.uptime(),
     connectedDevices: connectionManager.connectedDevices().length,
     timestamp: new Date().toISOString(),
@@ -0,0 +1,79 @@
+import { Router } from "express";
+import connectionManager from "../connectionManager.js";
+import { requireAuth } from "../auth.js";
+import logger from "../logger.js";
+
+const log = logger.child({ module: "routes/events" });
+const router = Router();
+
+/** Comment line sent periodically so proxies and clients keep the stream open. */
+const KEEPALIVE_INTERVAL_MS = 15_000;
+
+// ── GET /events ───────────────────────────────────────────────────────────────
+
+/**
+ * Server-Sent Events stream of device activity (used by `dial call --follow`,
+ * `dial conference`, `dial sms`, `dial ussd` and `dial watch`).
+ *
+ * Request:
+ *   GET /events[?deviceId=android_abc123]
+ *   Authorization: Bearer <token>
+ *
+ * Each event is a single `data:` line carrying a JSON object:
+ *   { type: "STATUS",       deviceId, state, number, commandId, timestamp }
+ *   { type: "SMS_STATUS",   deviceId, state, commandId, reason, timestamp }
+ *   { type: "USSD_RESPONSE", deviceId, commandId, ok, message, timestamp }
+ *   { type: "LEG_STATUS",   deviceId, number, state, conference, commandId, timestamp }
+ *   { type: "INCOMING",     deviceId, state, number, name, timestamp }
+ *   { type: "DISCONNECTED", deviceId, reason, timestamp }
+ *
+ * When `deviceId` is given only events for that device are forwarded.
+ */
+router.get("/events", requireAuth, (req, res) => {
+  const filter = typeof req.query.deviceId === "string" ? req.query.deviceId.trim() : "";
+
+  res.writeHead(200, {
+    "Content-Type": "text/event-stream",
+    "Cache-Control": "no-cache",
+    Connection: "keep-alive",
+  });
+  res.flushHeaders();
+
+  const send = (deviceId, event) => {
+    if (filter && deviceId !== filter) return;
+    const payload = { ...event, deviceId, timestamp: new Date().toISOString() };
+    res.write(`event: ${event.type.toLowerCase()}\ndata: ${JSON.stringify(payload)}\n\n`);
+  };
+
+  const onStatus = (deviceId, status) => send(deviceId, { type: "STATUS", ...status });
+  const onSmsStatus = (deviceId, status) => send(deviceId, { type: "SMS_STATUS", ...status });
+  const onUssdResponse = (deviceId, response) =>
+    send(deviceId, { type: "USSD_RESPONSE", ...response });
+  const onLegStatus = (deviceId, status) => send(deviceId, { type: "LEG_STATUS", ...status });
+  const onIncoming = (deviceId, call) => send(deviceId, { type: "INCOMING", ...call });
+  const onDisconnected = (deviceId, reason) =>
+    send(deviceId, { type: "DISCONNECTED", reason: reason || null });
+
+  connectionManager.on("status", onStatus);
+  connectionManager.on("smsStatus", onSmsStatus);
+  connectionManager.on("ussdResponse", onUssdResponse);
+  connectionManager.on("legStatus", onLegStatus);
+  connectionManager.on("incoming", onIncoming);
+  connectionManager.on("disconnected", onDisconnected);
+
+  const keepalive = setInterval(() => res.write(": keepalive\n\n"), KEEPALIVE_INTERVAL_MS);
+  log.info({ deviceId: filter || null }, "Event stream opened");
+
+  req.on("close", () => {
+    clearInterval(keepalive);
+    connectionManager.off("status", onStatus);
+    connectionManager.off("smsStatus", onSmsStatus);
+    connectionManager.off("ussdResponse", onUssdResponse);
+    connectionManager.off("legStatus", onLegStatus);
+    connectionManager.off("incoming", onIncoming);
+    connectionManager.off("disconnected", onDisconnected);
+    log.info({ deviceId: filter || null }, "Event stream closed");
+  });
+});
+
+export default router;
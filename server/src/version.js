@@ -0,0 +1,39 @@
+import { createRequire } from "module";
+
+const require = createRequire(import.meta.url);
+const pkg = require("../package.json");
+
+/** Gateway release, straight from package.json. */
+export const GATEWAY_VERSION = pkg.version;
+
+/**
+ * Version of the REST/WebSocket contract spoken with `dial` and the app.
+ *
+ * Bump this whenever the contract changes, so `dial` can tell which
+ * endpoints and events a gateway has.  Only a change that breaks clients
+ * speaking an older version (a field renamed, an endpoint dropped) also
+ * raises `MIN_PROTOCOL_VERSION`; the CLI refuses a gateway whose minimum is
+ * above its own version and merely warns about any other difference.
+ * Keep `PROTOCOL_VERSION` in `client/src/api.rs` in step.
+ *
+ *   1  GET /health, /events; POST /call waits for the device ACK
+ *   2  Idempotency-Key on commands
+ *   3  POST /hangup
+ *   4  POST /sms, SMS_STATUS events
+ *   5  POST /dtmf
+ *   6  POST /ussd, USSD_RESPONSE events
+ *   7  `sim` on /call, SIMs in /devices
+ *   8  `withholdCallerId` / `callerIdPrefix` on /call
+ *   9  POST /control
+ *  10  POST /merge, LEG_STATUS events
+ *  11  INCOMING events
+ *  12  POST /answer, /reject
+ *  13  CALL_ANSWERED status
+ */
+export const PROTOCOL_VERSION = 13;
+
+/**
+ * Oldest protocol a client may speak and still be served.  Every change so
+ * far was additive.
+ */
+export const MIN_PROTOCOL_VERSION = 1;